@@ -93,6 +93,7 @@ impl ToTokens for AttrTy {
     }
 }
 
+#[allow(clippy::never_loop)]
 fn resolve_attr(attr: TokenStream2) -> AttrTy {
     let attr_vals = AttrAlisa::parse_terminated.parse2(attr).unwrap();
     // println!("{:?}", attr_vals);
@@ -1,14 +1,20 @@
 use crate::comm::BytesWrap;
+use crate::error::{DcpError, Result};
 use crate::options::IpBlockInfo;
 use crate::options::{
-    BlockError, BlockInfo, BlockQualifier, InnerIpAddr, OptionAndSub, OptionAndSubValue,
+    BlockError, BlockInfo, BlockQualifier, InnerFullIpSuite, InnerIpAddr, OptionAndSub,
+    OptionAndSubValue, ResetFactoryMode,
 };
-use anyhow::{bail, Result};
+use crate::parse::{ParseContext, ParseWarning};
+use bytes::BufMut;
 
 pub trait BlockTrait {
     fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
     fn payload(&self) -> u16;
-    fn append_data(&self, data: &mut Vec<u8>);
+    fn append_data(&self, data: &mut impl BufMut);
 }
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct BlockPadding;
@@ -22,8 +28,8 @@ impl BlockTrait for BlockPadding {
         0
     }
 
-    fn append_data(&self, data: &mut Vec<u8>) {
-        data.push(0u8);
+    fn append_data(&self, data: &mut impl BufMut) {
+        data.put_u8(0u8);
     }
 }
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -43,10 +49,10 @@ impl BlockTrait for BlockOptionAndSub {
         0
     }
 
-    fn append_data(&self, data: &mut Vec<u8>) {
+    fn append_data(&self, data: &mut impl BufMut) {
         let (a, b) = self.0.to_u8s();
-        data.push(a);
-        data.push(b);
+        data.put_u8(a);
+        data.put_u8(b);
     }
 }
 
@@ -66,10 +72,14 @@ impl BlockIp {
         &self.info
     }
     pub fn try_from_bytes(value: BytesWrap) -> Result<Self> {
-        let val = value.slice(2..)?;
-        let len = Len::try_from(val.as_ref())?;
+        Self::parse(value, &mut ParseContext::default())
+    }
+    pub(crate) fn parse(value: BytesWrap, ctx: &mut ParseContext) -> Result<Self> {
+        let val = block_value(OptionAndSub::IpAddr, &value, 2)?;
         let info = IpBlockInfo::try_from(value.slice(4..=5)?)?;
-        let ip = InnerIpAddr::new(value.slice(6..(len.0 + 4))?)?;
+        check_ip_info(OptionAndSub::IpAddr, info, ctx)?;
+        let ip = InnerIpAddr::new(val.clone())?;
+        ctx.check_len(OptionAndSub::IpAddr, ip.payload_size(), val.len())?;
         Ok(Self { ip, info })
     }
 }
@@ -82,10 +92,54 @@ impl BlockTrait for BlockIp {
         14
     }
 
-    fn append_data(&self, data: &mut Vec<u8>) {
-        data.extend_from_slice(OptionAndSub::IpAddr.to_u8_array().as_slice());
-        data.extend_from_slice(&14u16.to_be_bytes());
-        data.extend_from_slice(self.info.to_u8_array().as_slice());
+    fn append_data(&self, data: &mut impl BufMut) {
+        data.put_slice(OptionAndSub::IpAddr.to_u8_array().as_slice());
+        data.put_slice(&14u16.to_be_bytes());
+        data.put_slice(self.info.to_u8_array().as_slice());
+        self.ip.append_value_to_data(data);
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct BlockFullIpSuite {
+    pub(crate) ip: InnerFullIpSuite,
+    pub(crate) info: IpBlockInfo,
+}
+impl BlockFullIpSuite {
+    pub fn new(ip: InnerFullIpSuite, info: IpBlockInfo) -> Self {
+        Self { ip, info }
+    }
+    pub fn ip(&self) -> &InnerFullIpSuite {
+        &self.ip
+    }
+    pub fn info(&self) -> &IpBlockInfo {
+        &self.info
+    }
+    pub fn try_from_bytes(value: BytesWrap) -> Result<Self> {
+        Self::parse(value, &mut ParseContext::default())
+    }
+    pub(crate) fn parse(value: BytesWrap, ctx: &mut ParseContext) -> Result<Self> {
+        let val = block_value(OptionAndSub::FullIpSuite, &value, 2)?;
+        let info = IpBlockInfo::try_from(value.slice(4..=5)?)?;
+        check_ip_info(OptionAndSub::FullIpSuite, info, ctx)?;
+        let ip = InnerFullIpSuite::new(val.clone())?;
+        ctx.check_len(OptionAndSub::FullIpSuite, ip.payload_size(), val.len())?;
+        Ok(Self { ip, info })
+    }
+}
+impl BlockTrait for BlockFullIpSuite {
+    fn len(&self) -> usize {
+        self.ip.payload_size() + 6
+    }
+
+    fn payload(&self) -> u16 {
+        30
+    }
+
+    fn append_data(&self, data: &mut impl BufMut) {
+        data.put_slice(OptionAndSub::FullIpSuite.to_u8_array().as_slice());
+        data.put_slice(&self.payload().to_be_bytes());
+        data.put_slice(self.info.to_u8_array().as_slice());
         self.ip.append_value_to_data(data);
     }
 }
@@ -100,6 +154,27 @@ impl BlockSet {
     pub fn new(option: OptionAndSubValue, qualifier: BlockQualifier) -> Self {
         Self { option, qualifier }
     }
+    pub fn try_new(option: OptionAndSubValue, qualifier: BlockQualifier) -> Result<Self> {
+        if let OptionAndSubValue::DHCP(value) = &option {
+            value.validate()?;
+        }
+        if let Some(expect) = option.control_qualifier() {
+            if !option.accepts_qualifier(&qualifier) {
+                return Err(DcpError::InvalidQualifier {
+                    option: option.option(),
+                    expected: expect,
+                    got: qualifier,
+                });
+            }
+        }
+        Ok(Self { option, qualifier })
+    }
+    pub fn new_control(option: OptionAndSubValue) -> Result<Self> {
+        match option.control_qualifier() {
+            Some(qualifier) => Ok(Self { option, qualifier }),
+            None => Err(DcpError::NotControlOption(option.option())),
+        }
+    }
     pub fn option(&self) -> &OptionAndSubValue {
         &self.option
     }
@@ -108,19 +183,29 @@ impl BlockSet {
     }
 }
 
-impl TryFrom<BytesWrap> for BlockSet {
-    type Error = anyhow::Error;
-    fn try_from(value: BytesWrap) -> Result<Self, Self::Error> {
+impl BlockSet {
+    pub(crate) fn parse(value: BytesWrap, ctx: &mut ParseContext) -> Result<Self> {
         let ty = OptionAndSub::try_from(value.clone())?;
-        let val = value.slice(2..)?;
-        let len = Len::try_from(val.as_ref())?;
+        let val = block_value(ty, &value, 2)?;
         let qualifier = BlockQualifier::try_from(value.slice(4..=5)?)?;
-        let val = value.slice(6..(len.0 + 4))?;
-        let option = OptionAndSubValue::init_by_ty(ty, val)?;
+        let option = match ty {
+            OptionAndSub::ResetFactory => {
+                OptionAndSubValue::ResetFactory(ResetFactoryMode::from(qualifier.to_u8_array()))
+            }
+            ty => OptionAndSubValue::init_by_ty(ty, val.clone())?,
+        };
+        ctx.check_len(ty, option.payload_size(), val.len())?;
         Ok(Self { option, qualifier })
     }
 }
 
+impl TryFrom<BytesWrap> for BlockSet {
+    type Error = DcpError;
+    fn try_from(value: BytesWrap) -> Result<Self, Self::Error> {
+        Self::parse(value, &mut ParseContext::default())
+    }
+}
+
 impl BlockTrait for BlockSet {
     fn len(&self) -> usize {
         self.option.payload_size() + 6
@@ -130,10 +215,10 @@ impl BlockTrait for BlockSet {
         (self.option.payload_size() + 2) as u16
     }
 
-    fn append_data(&self, data: &mut Vec<u8>) {
+    fn append_data(&self, data: &mut impl BufMut) {
         self.option.append_option_to_data(data);
-        data.extend_from_slice(self.payload().to_be_bytes().as_slice());
-        data.extend_from_slice(self.qualifier.to_u8_array().as_slice());
+        data.put_slice(self.payload().to_be_bytes().as_slice());
+        data.put_slice(self.qualifier.to_u8_array().as_slice());
         self.option.append_value_to_data(data);
     }
 }
@@ -161,11 +246,20 @@ impl BlockCommon {
         self.info = info;
     }
     pub fn try_from_bytes(ty: OptionAndSub, value: BytesWrap) -> Result<Self> {
-        let val = value.slice(2..)?;
-        let len = Len::try_from(val.as_ref())?;
+        Self::parse(ty, value, &mut ParseContext::default())
+    }
+    pub(crate) fn parse(
+        ty: OptionAndSub,
+        value: BytesWrap,
+        ctx: &mut ParseContext,
+    ) -> Result<Self> {
+        let val = block_value(ty, &value, 2)?;
         let info = BlockInfo::try_from(value.slice(4..=5)?)?;
-        let val = value.slice(6..(len.0 + 4))?;
-        let option = OptionAndSubValue::init_by_ty(ty, val)?;
+        if let BlockInfo::UnSupport(info) = info {
+            ctx.report(ParseWarning::UnknownBlockInfo { option: ty, info })?;
+        }
+        let option = OptionAndSubValue::init_by_ty(ty, val.clone())?;
+        ctx.check_len(ty, option.payload_size(), val.len())?;
         Ok(Self { option, info })
     }
 }
@@ -178,14 +272,14 @@ impl BlockTrait for BlockCommon {
         (self.option.payload_size() + 2) as u16
     }
 
-    fn append_data(&self, data: &mut Vec<u8>) {
+    fn append_data(&self, data: &mut impl BufMut) {
         self.option.append_option_to_data(data);
-        data.extend_from_slice(
+        data.put_slice(
             ((self.option.payload_size() + 2) as u16)
                 .to_be_bytes()
                 .as_slice(),
         );
-        data.extend_from_slice(self.info.to_u8_array().as_slice());
+        data.put_slice(self.info.to_u8_array().as_slice());
         self.option.append_value_to_data(data);
     }
 }
@@ -200,20 +294,28 @@ impl BlockTrait for BlockResp {
         3
     }
 
-    fn append_data(&self, data: &mut Vec<u8>) {
-        data.extend_from_slice(OptionAndSub::Response.to_u8_array().as_slice());
-        data.extend_from_slice(self.payload().to_be_bytes().as_slice());
-        data.extend_from_slice(self.0.to_u8_array().as_slice());
-        data.push(self.1 as u8);
+    fn append_data(&self, data: &mut impl BufMut) {
+        data.put_slice(OptionAndSub::Response.to_u8_array().as_slice());
+        data.put_slice(self.payload().to_be_bytes().as_slice());
+        data.put_slice(self.0.to_u8_array().as_slice());
+        data.put_u8(self.1 as u8);
+    }
+}
+
+impl BlockResp {
+    pub(crate) fn parse(value: BytesWrap, ctx: &mut ParseContext) -> Result<Self> {
+        let val = block_value(OptionAndSub::Response, &value, 0)?;
+        let ty = OptionAndSub::try_from(val.clone())?;
+        let error = val.slice(2..=2)?.as_ref()[0];
+        ctx.check_len(OptionAndSub::Response, 3, val.len())?;
+        Ok(Self(ty, BlockError::try_from(error)?))
     }
 }
 
 impl TryFrom<BytesWrap> for BlockResp {
-    type Error = anyhow::Error;
+    type Error = DcpError;
     fn try_from(value: BytesWrap) -> Result<Self, Self::Error> {
-        let ty = OptionAndSub::try_from(value.slice(4..=5)?)?;
-        let val = value.slice(6..=6)?.as_ref()[0];
-        Ok(Self(ty, BlockError::try_from(val)?))
+        Self::parse(value, &mut ParseContext::default())
     }
 }
 
@@ -226,14 +328,20 @@ impl From<OptionAndSubValue> for BlockCommonWithoutInfo {
     }
 }
 
+impl BlockCommonWithoutInfo {
+    pub(crate) fn parse(value: BytesWrap, ctx: &mut ParseContext) -> Result<Self> {
+        let ty = OptionAndSub::try_from(value.clone())?;
+        let val = block_value(ty, &value, 0)?;
+        let option = OptionAndSubValue::init_by_ty(ty, val.clone())?;
+        ctx.check_len(ty, option.payload_size(), val.len())?;
+        Ok(Self(option))
+    }
+}
+
 impl TryFrom<BytesWrap> for BlockCommonWithoutInfo {
-    type Error = anyhow::Error;
+    type Error = DcpError;
     fn try_from(value: BytesWrap) -> Result<Self, Self::Error> {
-        let ty = OptionAndSub::try_from(value.clone())?;
-        let val = value.slice(2..)?;
-        let len = Len::try_from(val.as_ref())?;
-        let val = value.slice(4..(len.0 + 4))?;
-        Ok(Self(OptionAndSubValue::init_by_ty(ty, val)?))
+        Self::parse(value, &mut ParseContext::default())
     }
 }
 
@@ -246,22 +354,51 @@ impl BlockTrait for BlockCommonWithoutInfo {
         self.0.payload_size() as u16
     }
 
-    fn append_data(&self, data: &mut Vec<u8>) {
+    fn append_data(&self, data: &mut impl BufMut) {
         self.0.append_option_to_data(data);
-        data.extend_from_slice((self.0.payload_size() as u16).to_be_bytes().as_slice());
+        data.put_slice((self.0.payload_size() as u16).to_be_bytes().as_slice());
         self.0.append_value_to_data(data);
     }
 }
 
 pub struct Len(pub usize);
 impl TryFrom<&[u8]> for Len {
-    type Error = anyhow::Error;
+    type Error = DcpError;
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
         if let Some(val) = value.get(0..=1) {
             let len = u16::from_be_bytes([val[0], val[1]]) as usize;
             Ok(Len(len))
         } else {
-            bail!("")
+            Err(DcpError::Truncated {
+                needed: 2,
+                got: value.len(),
+            })
         }
     }
 }
+
+// value of a block, behind option, block length and `skip` bytes of BlockInfo or BlockQualifier
+fn block_value(option: OptionAndSub, value: &BytesWrap, skip: usize) -> Result<BytesWrap> {
+    let declared = Len::try_from(value.slice(2..)?.as_ref())?.0;
+    let available = value.len().saturating_sub(4);
+    if declared < skip || declared > available {
+        return Err(DcpError::BadBlockLength {
+            option,
+            declared,
+            available,
+        });
+    }
+    value.slice((4 + skip)..(4 + declared))
+}
+
+// option, block length and the value as declared, without padding
+pub(crate) fn block_len(option: OptionAndSub, value: &BytesWrap) -> Result<usize> {
+    Ok(block_value(option, value, 0)?.len() + 4)
+}
+
+fn check_ip_info(option: OptionAndSub, info: IpBlockInfo, ctx: &mut ParseContext) -> Result<()> {
+    match info {
+        IpBlockInfo::UnSupport(info) => ctx.report(ParseWarning::UnknownBlockInfo { option, info }),
+        _ => Ok(()),
+    }
+}
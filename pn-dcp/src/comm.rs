@@ -1,10 +1,13 @@
-use anyhow::{bail, Result};
+use crate::error::{DcpError, Result};
 use bytes::Bytes;
 use std::ops::RangeBounds;
 
 use pnet::packet::ethernet::EtherType;
 
 pub const PROFINET_ETHER_TYPE: EtherType = EtherType(0x8892);
+pub const VLAN_ETHER_TYPE: EtherType = EtherType(0x8100);
+// without FCS
+pub const ETHERNET_MIN_FRAME_LEN: usize = 60;
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct BytesWrap(Bytes);
@@ -41,9 +44,15 @@ impl BytesWrap {
     pub fn len(&self) -> usize {
         self.0.len()
     }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
     pub fn split_off(&mut self, at: usize) -> Result<Self> {
         if at > self.len() {
-            bail!("split_off out of bounds: {:?} <= {:?}", at, self.len(),)
+            return Err(DcpError::Truncated {
+                needed: at,
+                got: self.len(),
+            });
         }
         Ok(Self(self.0.split_off(at)))
     }
@@ -54,26 +63,30 @@ impl BytesWrap {
 
         let begin = match range.start_bound() {
             Bound::Included(&n) => n,
-            Bound::Excluded(&n) => n + 1,
+            Bound::Excluded(&n) => n
+                .checked_add(1)
+                .ok_or(DcpError::InvalidRange { begin: n, end: n })?,
             Bound::Unbounded => 0,
         };
 
         let end = match range.end_bound() {
-            Bound::Included(&n) => n.checked_add(1).expect("out of range"),
+            Bound::Included(&n) => n
+                .checked_add(1)
+                .ok_or(DcpError::InvalidRange { begin, end: n })?,
             Bound::Excluded(&n) => n,
-            Bound::Unbounded => len,
+            // a start behind the data is missing data, not an inverted range
+            Bound::Unbounded => len.max(begin),
         };
         if begin > end {
-            bail!(
-                "range start must not be greater than end: {:?} <= {:?}",
-                begin,
-                end
-            );
+            return Err(DcpError::InvalidRange { begin, end });
         }
         if end > len {
-            bail!("range end out of bounds: {:?} <= {:?}", end, len);
+            return Err(DcpError::Truncated {
+                needed: end,
+                got: len,
+            });
         }
-        let bytes = self.0.slice(range);
+        let bytes = self.0.slice(begin..end);
         Ok(Self(bytes))
     }
 }
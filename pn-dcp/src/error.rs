@@ -0,0 +1,146 @@
+use crate::options::{BlockQualifier, IpSuiteError, NameOfStationError, OptionAndSub};
+use crate::packet::PnDcpTy;
+use crate::parse::ParseWarning;
+use std::fmt::{Display, Formatter};
+
+pub type Result<T, E = DcpError> = std::result::Result<T, E>;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DcpError {
+    // the data ends before `needed` bytes
+    Truncated {
+        needed: usize,
+        got: usize,
+    },
+    // a slice range that ends before it begins
+    InvalidRange {
+        begin: usize,
+        end: usize,
+    },
+    NotProfinet,
+    UnknownServiceType([u8; 4]),
+    UnexpectedServiceType {
+        expected: PnDcpTy,
+        got: PnDcpTy,
+    },
+    // block length field does not fit the data that follows it
+    BadBlockLength {
+        option: OptionAndSub,
+        declared: usize,
+        available: usize,
+    },
+    UnknownBlockError(u8),
+    UnexpectedBlock(OptionAndSub),
+    MissingBlock(OptionAndSub),
+    InvalidValue {
+        option: OptionAndSub,
+        value: Vec<u8>,
+    },
+    InvalidQualifier {
+        option: OptionAndSub,
+        expected: BlockQualifier,
+        got: BlockQualifier,
+    },
+    // the value does not fit its length field
+    ValueTooLong {
+        option: OptionAndSub,
+        max: usize,
+        got: usize,
+    },
+    NotControlOption(OptionAndSub),
+    XidMismatch {
+        expected: [u8; 4],
+        got: [u8; 4],
+    },
+    BufferTooSmall {
+        needed: usize,
+        got: usize,
+    },
+    InvalidDeviceId(String),
+    InvalidNameOfStation(NameOfStationError),
+    InvalidIpSuite(IpSuiteError),
+    // a warning rejected by strict parsing
+    Strict(ParseWarning),
+}
+
+impl Display for DcpError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Truncated { needed, got } => {
+                write!(f, "data too short: need {} bytes, got {}", needed, got)
+            }
+            Self::InvalidRange { begin, end } => {
+                write!(f, "invalid range: {} is behind {}", begin, end)
+            }
+            Self::NotProfinet => write!(f, "the packet is not a pn-dcp packet"),
+            Self::UnknownServiceType(a) => write!(f, "unknown pn-dcp service type: {:02x?}", a),
+            Self::UnexpectedServiceType { expected, got } => {
+                write!(f, "expect pn-dcp {:?}, got {:?}", expected, got)
+            }
+            Self::BadBlockLength {
+                option,
+                declared,
+                available,
+            } => write!(
+                f,
+                "block {:?} declares {} bytes, but {} are available",
+                option, declared, available
+            ),
+            Self::UnknownBlockError(a) => write!(f, "unknown block error: {:#04x}", a),
+            Self::UnexpectedBlock(option) => write!(f, "unexpected block {:?}", option),
+            Self::MissingBlock(option) => write!(f, "not contain block {:?}", option),
+            Self::InvalidValue { option, value } => {
+                write!(f, "invalid value of {:?}: {:02x?}", option, value)
+            }
+            Self::InvalidQualifier {
+                option,
+                expected,
+                got,
+            } => write!(
+                f,
+                "{:?} must be set with qualifier {:?}, not {:?}",
+                option, expected, got
+            ),
+            Self::ValueTooLong { option, max, got } => write!(
+                f,
+                "value of {:?} has {} bytes, at most {} fit",
+                option, got, max
+            ),
+            Self::NotControlOption(option) => write!(f, "{:?} is not a control option", option),
+            Self::XidMismatch { expected, got } => {
+                write!(f, "xid {:02x?} does not match {:02x?}", got, expected)
+            }
+            Self::BufferTooSmall { needed, got } => {
+                write!(f, "buffer too small: need {} bytes, got {}", needed, got)
+            }
+            Self::InvalidDeviceId(a) => {
+                write!(f, "device id must be <vendor id>/<device id>: {}", a)
+            }
+            Self::InvalidNameOfStation(e) => write!(f, "invalid name of station: {}", e),
+            Self::InvalidIpSuite(e) => write!(f, "invalid ip suite: {}", e),
+            Self::Strict(w) => write!(f, "rejected by strict parsing: {}", w),
+        }
+    }
+}
+
+impl std::error::Error for DcpError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidNameOfStation(e) => Some(e),
+            Self::InvalidIpSuite(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<NameOfStationError> for DcpError {
+    fn from(e: NameOfStationError) -> Self {
+        Self::InvalidNameOfStation(e)
+    }
+}
+
+impl From<IpSuiteError> for DcpError {
+    fn from(e: IpSuiteError) -> Self {
+        Self::InvalidIpSuite(e)
+    }
+}
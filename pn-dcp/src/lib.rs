@@ -1,4 +1,7 @@
 pub mod block;
 pub mod comm;
+pub mod error;
 pub mod options;
 pub mod packet;
+pub mod parse;
+pub mod view;
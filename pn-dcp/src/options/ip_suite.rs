@@ -0,0 +1,167 @@
+use crate::options::{InnerFullIpSuite, InnerIpAddr};
+use std::fmt::{Display, Formatter};
+use std::net::Ipv4Addr;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum IpSuiteError {
+    NonContiguousMask(Ipv4Addr),
+    // prefix 0, 31 and 32 leave no usable host address
+    UnusableMask {
+        mask: Ipv4Addr,
+        prefix: u32,
+    },
+    InvalidAddress(Ipv4Addr),
+    NetworkAddress {
+        ip: Ipv4Addr,
+        network: Ipv4Addr,
+    },
+    BroadcastAddress {
+        ip: Ipv4Addr,
+        broadcast: Ipv4Addr,
+    },
+    GatewayOutsideSubnet {
+        gateway: Ipv4Addr,
+        network: Ipv4Addr,
+        mask: Ipv4Addr,
+    },
+    GatewayNetworkAddress(Ipv4Addr),
+    GatewayBroadcastAddress(Ipv4Addr),
+}
+
+impl Display for IpSuiteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NonContiguousMask(mask) => {
+                write!(f, "subnet mask {} is not contiguous", mask)
+            }
+            Self::UnusableMask { mask, prefix } => write!(
+                f,
+                "subnet mask {} (/{}) leaves no usable host address",
+                mask, prefix
+            ),
+            Self::InvalidAddress(ip) => write!(
+                f,
+                "{} is not a unicast host address (unspecified, loopback, multicast or broadcast)",
+                ip
+            ),
+            Self::NetworkAddress { ip, network } => {
+                write!(f, "{} is the network address of {}", ip, network)
+            }
+            Self::BroadcastAddress { ip, broadcast } => {
+                write!(f, "{} is the broadcast address {}", ip, broadcast)
+            }
+            Self::GatewayOutsideSubnet {
+                gateway,
+                network,
+                mask,
+            } => write!(
+                f,
+                "gateway {} is outside the subnet {}/{}",
+                gateway, network, mask
+            ),
+            Self::GatewayNetworkAddress(gateway) => {
+                write!(
+                    f,
+                    "gateway {} is the network address of the subnet",
+                    gateway
+                )
+            }
+            Self::GatewayBroadcastAddress(gateway) => {
+                write!(
+                    f,
+                    "gateway {} is the broadcast address of the subnet",
+                    gateway
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for IpSuiteError {}
+
+impl InnerIpAddr {
+    pub fn try_new_by_ipv4(
+        ip: Ipv4Addr,
+        subnetmask: Ipv4Addr,
+        gateway: Ipv4Addr,
+    ) -> Result<Self, IpSuiteError> {
+        let ip = Self::new_by_ipv4(ip, subnetmask, gateway);
+        ip.validate()?;
+        Ok(ip)
+    }
+    pub fn ip(&self) -> Ipv4Addr {
+        self.0
+    }
+    pub fn subnetmask(&self) -> Ipv4Addr {
+        self.1
+    }
+    pub fn gateway(&self) -> Ipv4Addr {
+        self.2
+    }
+    pub fn prefix_len(&self) -> Option<u32> {
+        let mask = u32::from(self.1);
+        // contiguous: all ones followed by all zeros
+        if mask.leading_ones() + mask.trailing_zeros() == 32 {
+            Some(mask.leading_ones())
+        } else {
+            None
+        }
+    }
+    pub fn network(&self) -> Ipv4Addr {
+        Ipv4Addr::from(u32::from(self.0) & u32::from(self.1))
+    }
+    pub fn broadcast(&self) -> Ipv4Addr {
+        Ipv4Addr::from(u32::from(self.0) | !u32::from(self.1))
+    }
+    // 0.0.0.0/0.0.0.0/0.0.0.0 resets the ip of the device
+    pub fn is_reset(&self) -> bool {
+        [self.0, self.1, self.2].iter().all(|x| x.is_unspecified())
+    }
+    // a gateway of 0.0.0.0 means no gateway
+    pub fn validate(&self) -> Result<(), IpSuiteError> {
+        if self.is_reset() {
+            return Ok(());
+        }
+        let InnerIpAddr(ip, mask, gateway) = *self;
+        let prefix = self
+            .prefix_len()
+            .ok_or(IpSuiteError::NonContiguousMask(mask))?;
+        if prefix == 0 || prefix > 30 {
+            return Err(IpSuiteError::UnusableMask { mask, prefix });
+        }
+        if ip.is_unspecified() || ip.is_loopback() || ip.is_multicast() || ip.is_broadcast() {
+            return Err(IpSuiteError::InvalidAddress(ip));
+        }
+        let network = self.network();
+        let broadcast = self.broadcast();
+        if ip == network {
+            return Err(IpSuiteError::NetworkAddress { ip, network });
+        }
+        if ip == broadcast {
+            return Err(IpSuiteError::BroadcastAddress { ip, broadcast });
+        }
+        if gateway.is_unspecified() {
+            return Ok(());
+        }
+        if u32::from(gateway) & u32::from(mask) != u32::from(network) {
+            return Err(IpSuiteError::GatewayOutsideSubnet {
+                gateway,
+                network,
+                mask,
+            });
+        }
+        if gateway == network {
+            return Err(IpSuiteError::GatewayNetworkAddress(gateway));
+        }
+        if gateway == broadcast {
+            return Err(IpSuiteError::GatewayBroadcastAddress(gateway));
+        }
+        Ok(())
+    }
+}
+
+impl InnerFullIpSuite {
+    pub fn validate(&self) -> Result<(), IpSuiteError> {
+        self.0.validate()
+    }
+}
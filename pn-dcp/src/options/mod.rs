@@ -1,8 +1,18 @@
+mod ip_suite;
+mod name_of_station;
+mod punycode;
+
+pub use ip_suite::IpSuiteError;
+pub use name_of_station::{NameOfStation, NameOfStationError};
+
 use crate::comm::BytesWrap;
+use crate::error::{DcpError, Result};
 use crate::packet::ident_resp::PacketIdentResp;
-use anyhow::{bail, Result};
-use std::fmt::{Debug, Formatter};
+use bytes::BufMut;
+use pnet::util::MacAddr;
+use std::fmt::{Debug, Display, Formatter};
 use std::net::Ipv4Addr;
+use std::str::FromStr;
 
 // static const value_string pn_dcp_block_info[] = {
 // { 0x0000, "RESERVED" },
@@ -18,13 +28,13 @@ impl BlockInfo {
     pub fn to_u8_array(&self) -> [u8; 2] {
         match self {
             Self::Reserved => [0x00, 0x00],
-            Self::UnSupport(a) => a.clone(),
+            Self::UnSupport(a) => *a,
         }
     }
 }
 
 impl TryFrom<BytesWrap> for BlockInfo {
-    type Error = anyhow::Error;
+    type Error = DcpError;
 
     fn try_from(value: BytesWrap) -> std::result::Result<Self, Self::Error> {
         let val = value.slice(0..=1)?;
@@ -48,7 +58,7 @@ pub enum BlockQualifier {
 impl BlockQualifier {
     pub fn to_u8_array(&self) -> [u8; 2] {
         match self {
-            Self::UnSupport(a) => a.clone(),
+            Self::UnSupport(a) => *a,
             Self::UseTemporary => USE_TEMPORARY,
             Self::SavePermanent => SAVE_PERMANENT,
         }
@@ -69,19 +79,91 @@ impl Debug for BlockQualifier {
         }
     }
 }
+impl From<[u8; 2]> for BlockQualifier {
+    fn from(a: [u8; 2]) -> Self {
+        match a {
+            USE_TEMPORARY => Self::UseTemporary,
+            SAVE_PERMANENT => Self::SavePermanent,
+            b => Self::UnSupport(b),
+        }
+    }
+}
 impl TryFrom<BytesWrap> for BlockQualifier {
-    type Error = anyhow::Error;
+    type Error = DcpError;
+
+    fn try_from(value: BytesWrap) -> std::result::Result<Self, Self::Error> {
+        let val = value.slice(0..=1)?;
+        Ok(Self::from([val.as_ref()[0], val.as_ref()[1]]))
+    }
+}
+const SIGNAL_FLASH_ONCE: [u8; 2] = [0x01, 0x00];
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum SignalValue {
+    FlashOnce,
+    UnSupport([u8; 2]),
+}
+impl SignalValue {
+    pub fn to_u8_array(&self) -> [u8; 2] {
+        match self {
+            Self::FlashOnce => SIGNAL_FLASH_ONCE,
+            Self::UnSupport(a) => *a,
+        }
+    }
+}
+impl TryFrom<BytesWrap> for SignalValue {
+    type Error = DcpError;
 
     fn try_from(value: BytesWrap) -> std::result::Result<Self, Self::Error> {
         let val = value.slice(0..=1)?;
         let a = [val.as_ref()[0], val.as_ref()[1]];
         Ok(match a {
-            USE_TEMPORARY => Self::UseTemporary,
-            SAVE_PERMANENT => Self::SavePermanent,
+            SIGNAL_FLASH_ONCE => Self::FlashOnce,
             b => Self::UnSupport(b),
         })
     }
 }
+
+// ResetToFactory carries its mode in the BlockQualifier: bit 0 reserved, bit 1-15 mode
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum ResetFactoryMode {
+    ResetApplicationData,
+    ResetCommunication,
+    ResetEngineering,
+    ResetAllData,
+    ResetDevice,
+    ResetAndRestore,
+    UnSupport([u8; 2]),
+}
+impl ResetFactoryMode {
+    pub fn to_u8_array(&self) -> [u8; 2] {
+        match self {
+            Self::ResetApplicationData => [0x00, 0x02],
+            Self::ResetCommunication => [0x00, 0x04],
+            Self::ResetEngineering => [0x00, 0x06],
+            Self::ResetAllData => [0x00, 0x08],
+            Self::ResetDevice => [0x00, 0x10],
+            Self::ResetAndRestore => [0x00, 0x12],
+            Self::UnSupport(a) => *a,
+        }
+    }
+    pub fn to_qualifier(&self) -> BlockQualifier {
+        BlockQualifier::from(self.to_u8_array())
+    }
+}
+impl From<[u8; 2]> for ResetFactoryMode {
+    fn from(a: [u8; 2]) -> Self {
+        match a {
+            [0x00, 0x02] => Self::ResetApplicationData,
+            [0x00, 0x04] => Self::ResetCommunication,
+            [0x00, 0x06] => Self::ResetEngineering,
+            [0x00, 0x08] => Self::ResetAllData,
+            [0x00, 0x10] => Self::ResetDevice,
+            [0x00, 0x12] => Self::ResetAndRestore,
+            b => Self::UnSupport(b),
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct InnerIpAddr(pub Ipv4Addr, pub Ipv4Addr, pub Ipv4Addr);
 impl InnerIpAddr {
@@ -97,10 +179,10 @@ impl InnerIpAddr {
     pub fn new_by_ipv4(ip: Ipv4Addr, subnetmask: Ipv4Addr, gateway: Ipv4Addr) -> Self {
         Self(ip, subnetmask, gateway)
     }
-    pub fn append_value_to_data(&self, data: &mut Vec<u8>) {
-        data.extend_from_slice(self.0.octets().as_slice());
-        data.extend_from_slice(self.1.octets().as_slice());
-        data.extend_from_slice(self.2.octets().as_slice());
+    pub fn append_value_to_data(&self, data: &mut impl BufMut) {
+        data.put_slice(self.0.octets().as_slice());
+        data.put_slice(self.1.octets().as_slice());
+        data.put_slice(self.2.octets().as_slice());
     }
     pub fn payload_size(&self) -> usize {
         12
@@ -114,8 +196,50 @@ impl InnerIpAddr {
     }
 }
 
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct InnerFullIpSuite(pub InnerIpAddr, pub [Ipv4Addr; 4]);
+impl InnerFullIpSuite {
+    pub fn new(data: BytesWrap) -> Result<Self> {
+        let ip = InnerIpAddr::new(data.slice(0..=11)?)?;
+        let val = data.slice(12..=27)?;
+        let val = val.as_ref();
+        let mut dns = [Ipv4Addr::UNSPECIFIED; 4];
+        for (index, addr) in dns.iter_mut().enumerate() {
+            let i = index * 4;
+            *addr = Ipv4Addr::new(val[i], val[i + 1], val[i + 2], val[i + 3]);
+        }
+        Ok(Self(ip, dns))
+    }
+    pub fn new_by_ipv4(ip: InnerIpAddr, dns: [Ipv4Addr; 4]) -> Self {
+        Self(ip, dns)
+    }
+    pub fn ip(&self) -> &InnerIpAddr {
+        &self.0
+    }
+    pub fn dns(&self) -> &[Ipv4Addr; 4] {
+        &self.1
+    }
+    pub fn append_value_to_data(&self, data: &mut impl BufMut) {
+        self.0.append_value_to_data(data);
+        for dns in self.1.iter() {
+            data.put_slice(dns.octets().as_slice());
+        }
+    }
+    pub fn payload_size(&self) -> usize {
+        28
+    }
+    pub fn to_option(self) -> OptionAndSubValue {
+        OptionAndSubValue::FullIpSuite(self)
+    }
+
+    pub fn append_to_ident_resp(self, packet: &mut PacketIdentResp, info: IpBlockInfo) {
+        packet.append_block_full_ip_suite(self, info)
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct Response(pub OptionAndSub, pub BlockError);
+#[allow(clippy::len_without_is_empty)]
 impl Response {
     pub fn len(&self) -> usize {
         5
@@ -123,9 +247,9 @@ impl Response {
     pub fn payload(&self) -> u16 {
         3
     }
-    pub fn append_value_to_data(&self, data: &mut Vec<u8>) {
-        data.extend_from_slice(self.0.to_u8_array().as_slice());
-        data.push(self.1 as u8);
+    pub fn append_value_to_data(&self, data: &mut impl BufMut) {
+        data.put_slice(self.0.to_u8_array().as_slice());
+        data.put_u8(self.1 as u8);
     }
     pub fn to_option(self) -> OptionAndSubValue {
         OptionAndSubValue::Response(self)
@@ -133,7 +257,7 @@ impl Response {
 }
 
 impl TryFrom<BytesWrap> for Response {
-    type Error = anyhow::Error;
+    type Error = DcpError;
     fn try_from(data: BytesWrap) -> Result<Self, Self::Error> {
         let val = data.slice(0..=2)?;
         let ref_u8 = val.as_ref();
@@ -144,6 +268,317 @@ impl TryFrom<BytesWrap> for Response {
     }
 }
 
+const DHCP_HOST_NAME: u8 = 12;
+const DHCP_CLIENT_ID: u8 = 61;
+const DHCP_CONTROL: u8 = 255;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum DhcpClientId {
+    MacAddr,
+    NameOfStation,
+    Arbitrary(BytesWrap),
+    // the whole value, kept as received
+    Other(BytesWrap),
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum DhcpControl {
+    Disable,
+    Enable,
+    UnSupport(u8),
+}
+impl DhcpControl {
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            Self::Disable => 0x00,
+            Self::Enable => 0x01,
+            Self::UnSupport(a) => *a,
+        }
+    }
+}
+impl From<u8> for DhcpControl {
+    fn from(a: u8) -> Self {
+        match a {
+            0x00 => Self::Disable,
+            0x01 => Self::Enable,
+            b => Self::UnSupport(b),
+        }
+    }
+}
+
+// DHCP suboption data = DHCPParameterLength(u8) + DHCPParameterValue
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum DhcpValue {
+    HostName(BytesWrap),
+    ClientId(DhcpClientId),
+    Control(DhcpControl),
+    // unknown suboptions and values that do not fit their suboption
+    Other(u8, BytesWrap),
+}
+impl DhcpValue {
+    // DHCPParameterLength is one byte
+    pub const MAX_LEN: usize = 255;
+
+    pub fn host_name(name: &[u8]) -> Result<Self> {
+        let value = Self::HostName(name.into());
+        value.validate()?;
+        Ok(value)
+    }
+    pub fn client_id(id: &[u8]) -> Result<Self> {
+        let value = Self::ClientId(DhcpClientId::Arbitrary(id.into()));
+        value.validate()?;
+        Ok(value)
+    }
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            Self::ClientId(DhcpClientId::Other(a)) | Self::Other(_, a) => {
+                return Err(DcpError::InvalidValue {
+                    option: OptionAndSub::DHCP(self.suboption()),
+                    value: a.as_ref().to_vec(),
+                })
+            }
+            _ => {}
+        }
+        let len = self.payload_size() - 1;
+        if len > Self::MAX_LEN {
+            return Err(DcpError::ValueTooLong {
+                option: OptionAndSub::DHCP(self.suboption()),
+                max: Self::MAX_LEN,
+                got: len,
+            });
+        }
+        Ok(())
+    }
+    pub fn new(suboption: u8, data: BytesWrap) -> Result<Self> {
+        let len = data.slice(0..=0)?.as_ref()[0] as usize;
+        let val = data.slice(1..(len + 1))?;
+        Ok(match suboption {
+            DHCP_HOST_NAME => Self::HostName(val),
+            DHCP_CLIENT_ID => match val.as_ref() {
+                [0x01] => Self::ClientId(DhcpClientId::MacAddr),
+                [0x00] => Self::ClientId(DhcpClientId::NameOfStation),
+                [0x00, ..] => Self::ClientId(DhcpClientId::Arbitrary(val.slice(1..)?)),
+                _ => Self::ClientId(DhcpClientId::Other(val)),
+            },
+            DHCP_CONTROL if val.len() == 1 => Self::Control(DhcpControl::from(val.as_ref()[0])),
+            _ => Self::Other(suboption, val),
+        })
+    }
+    pub fn suboption(&self) -> u8 {
+        match self {
+            Self::HostName(_) => DHCP_HOST_NAME,
+            Self::ClientId(_) => DHCP_CLIENT_ID,
+            Self::Control(_) => DHCP_CONTROL,
+            Self::Other(a, _) => *a,
+        }
+    }
+    pub fn append_value_to_data(&self, data: &mut impl BufMut) {
+        data.put_u8((self.payload_size() - 1) as u8);
+        match self {
+            Self::HostName(a) => data.put_slice(a.as_ref()),
+            Self::ClientId(DhcpClientId::MacAddr) => data.put_u8(0x01),
+            Self::ClientId(DhcpClientId::NameOfStation) => data.put_u8(0x00),
+            Self::ClientId(DhcpClientId::Arbitrary(a)) => {
+                data.put_u8(0x00);
+                data.put_slice(a.as_ref());
+            }
+            Self::Control(a) => data.put_u8(a.to_u8()),
+            Self::ClientId(DhcpClientId::Other(a)) | Self::Other(_, a) => {
+                data.put_slice(a.as_ref())
+            }
+        }
+    }
+    pub fn payload_size(&self) -> usize {
+        match self {
+            Self::HostName(a) => a.len() + 1,
+            Self::ClientId(DhcpClientId::Arbitrary(a)) => a.len() + 2,
+            Self::ClientId(DhcpClientId::Other(a)) | Self::Other(_, a) => a.len() + 1,
+            Self::ClientId(_) => 2,
+            Self::Control(_) => 2,
+        }
+    }
+    pub fn to_option(self) -> OptionAndSubValue {
+        OptionAndSubValue::DHCP(self)
+    }
+}
+
+// StandardGatewayValue: whether the router of the IP suite is used as the standard gateway
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum StandardGatewayValue {
+    NoGateway,
+    Gateway,
+    UnSupport(u16),
+}
+impl StandardGatewayValue {
+    pub fn to_u16(&self) -> u16 {
+        match self {
+            Self::NoGateway => 0x0000,
+            Self::Gateway => 0x0001,
+            Self::UnSupport(a) => *a,
+        }
+    }
+}
+impl From<u16> for StandardGatewayValue {
+    fn from(a: u16) -> Self {
+        match a {
+            0x0000 => Self::NoGateway,
+            0x0001 => Self::Gateway,
+            b => Self::UnSupport(b),
+        }
+    }
+}
+
+// DeviceInitiativeValue: bit 0 = the device issues a Hello request after power on
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Default)]
+pub struct DeviceInitiative(pub u16);
+impl DeviceInitiative {
+    pub fn new(issues_hello: bool) -> Self {
+        Self(issues_hello as u16)
+    }
+    pub fn issues_hello(&self) -> bool {
+        self.0 & 0x0001 == 0x0001
+    }
+    pub fn to_option(self) -> OptionAndSubValue {
+        OptionAndSubValue::DeviceInitiative(self)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash, PartialOrd, Ord)]
+pub struct DeviceId {
+    pub vendor_id: u16,
+    pub device_id: u16,
+}
+impl DeviceId {
+    pub fn new(vendor_id: u16, device_id: u16) -> Self {
+        Self {
+            vendor_id,
+            device_id,
+        }
+    }
+    pub fn append_value_to_data(&self, data: &mut impl BufMut) {
+        data.put_slice(self.vendor_id.to_be_bytes().as_slice());
+        data.put_slice(self.device_id.to_be_bytes().as_slice());
+    }
+    pub fn to_option(self) -> OptionAndSubValue {
+        OptionAndSubValue::DeviceId(self)
+    }
+}
+impl TryFrom<BytesWrap> for DeviceId {
+    type Error = DcpError;
+
+    fn try_from(value: BytesWrap) -> std::result::Result<Self, Self::Error> {
+        let val = value.slice(0..=3)?;
+        let val = val.as_ref();
+        Ok(Self::new(
+            u16::from_be_bytes([val[0], val[1]]),
+            u16::from_be_bytes([val[2], val[3]]),
+        ))
+    }
+}
+impl Display for DeviceId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{:04X}/0x{:04X}", self.vendor_id, self.device_id)
+    }
+}
+impl FromStr for DeviceId {
+    type Err = DcpError;
+
+    // "0x002A/0x0401", the "0x" prefix is optional
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let parse = |id: &str| {
+            let id = id.trim();
+            let id = id
+                .strip_prefix("0x")
+                .or_else(|| id.strip_prefix("0X"))
+                .unwrap_or(id);
+            u16::from_str_radix(id, 16).map_err(|_| DcpError::InvalidDeviceId(s.to_string()))
+        };
+        match s.split_once('/') {
+            Some((vendor_id, device_id)) => Ok(Self::new(parse(vendor_id)?, parse(device_id)?)),
+            None => Err(DcpError::InvalidDeviceId(s.to_string())),
+        }
+    }
+}
+
+// DeviceRoleDetails + reserved, bits that are not known are kept as they are
+#[derive(Eq, PartialEq, Copy, Clone, Default, Hash)]
+pub struct DeviceRole {
+    bits: u8,
+    reserved: u8,
+}
+impl DeviceRole {
+    pub const IO_DEVICE: Self = Self::from_bits(0x01);
+    pub const IO_CONTROLLER: Self = Self::from_bits(0x02);
+    pub const IO_MULTIDEVICE: Self = Self::from_bits(0x04);
+    pub const PN_SUPERVISOR: Self = Self::from_bits(0x08);
+    const NAMES: [(Self, &'static str); 4] = [
+        (Self::IO_DEVICE, "IO-Device"),
+        (Self::IO_CONTROLLER, "IO-Controller"),
+        (Self::IO_MULTIDEVICE, "IO-Multidevice"),
+        (Self::PN_SUPERVISOR, "PN-Supervisor"),
+    ];
+
+    pub const fn from_bits(bits: u8) -> Self {
+        Self { bits, reserved: 0 }
+    }
+    pub fn bits(&self) -> u8 {
+        self.bits
+    }
+    pub fn reserved(&self) -> u8 {
+        self.reserved
+    }
+    pub fn contains(&self, other: Self) -> bool {
+        self.bits & other.bits == other.bits
+    }
+    pub fn insert(&mut self, other: Self) {
+        self.bits |= other.bits;
+    }
+    pub fn remove(&mut self, other: Self) {
+        self.bits &= !other.bits;
+    }
+    pub fn to_option(self) -> OptionAndSubValue {
+        OptionAndSubValue::DeviceRole(self)
+    }
+}
+impl std::ops::BitOr for DeviceRole {
+    type Output = Self;
+
+    fn bitor(mut self, rhs: Self) -> Self::Output {
+        self.insert(rhs);
+        self
+    }
+}
+impl Display for DeviceRole {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut unknown = self.bits;
+        let mut first = true;
+        for (role, name) in Self::NAMES.iter() {
+            if self.contains(*role) {
+                if !first {
+                    write!(f, " | ")?;
+                }
+                write!(f, "{}", name)?;
+                unknown &= !role.bits;
+                first = false;
+            }
+        }
+        if unknown != 0 {
+            if !first {
+                write!(f, " | ")?;
+            }
+            write!(f, "{:#04x}", unknown)?;
+        } else if first {
+            write!(f, "None")?;
+        }
+        Ok(())
+    }
+}
+impl Debug for DeviceRole {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DeviceRole({})", self)
+    }
+}
+
 #[derive(Default)]
 pub struct DeviceOptionsBuilder(Vec<OptionAndSub>);
 
@@ -159,100 +594,140 @@ impl DeviceOptionsBuilder {
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum OptionAndSubValue {
-    // MarAddr([u8; 6]),
+    MacAddr(MacAddr),
     IpAddr(InnerIpAddr),
-    // FullIpSuite(Ipv4Addr, Ipv4Addr, Ipv4Addr, Ipv4Addr),
+    FullIpSuite(InnerFullIpSuite),
     ManufacturerSpecific(BytesWrap),
-    NameOfStation(BytesWrap),
-    DeviceId([u8; 2], [u8; 2]),
-    DeviceRole(u8, u8), //DeviceRoleDetails + reserved
+    NameOfStation(BytesWrap), // raw, see NameOfStation for the validated name
+    DeviceId(DeviceId),
+    DeviceRole(DeviceRole),
     DeviceOptions(Vec<OptionAndSub>),
-    // AliasName, //Filter only?
-    // StartTransaction,
-    // EndTransaction,
-    // Signal,
+    AliasName(BytesWrap),
+    DeviceInstance(u8, u8), // high + low
+    OEMDeviceId(DeviceId),
+    StandardGateway(StandardGatewayValue),
+    RSIProperties(u16),
+    StartTransaction,
+    EndTransaction,
+    Signal(SignalValue),
     Response(Response), // not support yet
-                        // ResetFactory,
-                        // DevicecInitiative,
-                        All,
-                        // DHCP(u8),
-                        // LLDP(u8),
-                        Other(OptionAndSub, BytesWrap),
+    ResetFactory(ResetFactoryMode), // the mode is carried by the BlockQualifier
+    DeviceInitiative(DeviceInitiative),
+    All,
+    DHCP(DhcpValue),
+    // LLDP(u8),
+    Other(OptionAndSub, BytesWrap),
 }
 
 impl OptionAndSubValue {
-    pub fn append_option_to_data(&self, data: &mut Vec<u8>) {
+    // AliasName = LLDP PortID + "." + LLDP ChassisID, e.g. "port-001.chassis"
+    pub fn alias_name(port_id: &str, chassis_id: &str) -> Self {
+        Self::AliasName(format!("{}.{}", port_id, chassis_id).into_bytes().into())
+    }
+    pub fn option(&self) -> OptionAndSub {
         match self {
-            Self::IpAddr(_) => {
-                data.extend_from_slice(OptionAndSub::IpAddr.to_u8_array().as_slice())
-            }
-            // Self::FullIpSuite(_, _, _, _) => 16,
-            Self::ManufacturerSpecific(_) => {
-                data.extend_from_slice(OptionAndSub::ManufacturerSpecific.to_u8_array().as_slice())
+            Self::MacAddr(_) => OptionAndSub::MacAddr,
+            Self::IpAddr(_) => OptionAndSub::IpAddr,
+            Self::FullIpSuite(_) => OptionAndSub::FullIpSuite,
+            Self::ManufacturerSpecific(_) => OptionAndSub::ManufacturerSpecific,
+            Self::NameOfStation(_) => OptionAndSub::NameOfStation,
+            Self::DeviceId(_) => OptionAndSub::DeviceId,
+            Self::DeviceRole(_) => OptionAndSub::DeviceRole,
+            Self::DeviceOptions(_) => OptionAndSub::DeviceOptions,
+            Self::AliasName(_) => OptionAndSub::AliasName,
+            Self::DeviceInstance(_, _) => OptionAndSub::DeviceInstance,
+            Self::OEMDeviceId(_) => OptionAndSub::OEMDeviceId,
+            Self::StandardGateway(_) => OptionAndSub::StandardGateway,
+            Self::RSIProperties(_) => OptionAndSub::RSIProperties,
+            Self::DHCP(a) => OptionAndSub::DHCP(a.suboption()),
+            Self::StartTransaction => OptionAndSub::StartTransaction,
+            Self::EndTransaction => OptionAndSub::EndTransaction,
+            Self::Signal(_) => OptionAndSub::Signal,
+            Self::Response(_) => OptionAndSub::Response,
+            Self::ResetFactory(_) => OptionAndSub::ResetFactory,
+            Self::DeviceInitiative(_) => OptionAndSub::DeviceInitiative,
+            Self::All => OptionAndSub::All,
+            Self::Other(a, _) => *a,
+        }
+    }
+    pub fn append_option_to_data(&self, data: &mut impl BufMut) {
+        data.put_slice(self.option().to_u8_array().as_slice());
+    }
+    pub fn append_value_to_data(&self, data: &mut impl BufMut) {
+        match self {
+            Self::MacAddr(a) => {
+                data.put_slice(a.octets().as_slice());
             }
-            Self::NameOfStation(_) => {
-                data.extend_from_slice(OptionAndSub::NameOfStation.to_u8_array().as_slice())
+            Self::IpAddr(a) => {
+                data.put_slice(a.0.octets().as_slice());
+                data.put_slice(a.1.octets().as_slice());
+                data.put_slice(a.2.octets().as_slice());
             }
-            Self::DeviceId(_, _) => {
-                data.extend_from_slice(OptionAndSub::DeviceId.to_u8_array().as_slice())
+            Self::FullIpSuite(a) => {
+                a.append_value_to_data(data);
             }
-            Self::DeviceRole(_, _) => {
-                data.extend_from_slice(OptionAndSub::DeviceRole.to_u8_array().as_slice())
+            Self::ManufacturerSpecific(val) => {
+                data.put_slice(val.as_ref());
             }
-            Self::DeviceOptions(_) => {
-                data.extend_from_slice(OptionAndSub::DeviceOptions.to_u8_array().as_slice())
+            Self::NameOfStation(val) => {
+                data.put_slice(val.as_ref());
             }
-            Self::Response(_) => {
-                data.extend_from_slice(OptionAndSub::Response.to_u8_array().as_slice())
+            Self::AliasName(val) => {
+                data.put_slice(val.as_ref());
             }
-            Self::All => {
-                data.extend_from_slice(OptionAndSub::All.to_u8_array().as_slice())
+            Self::DeviceInstance(a, b) => {
+                data.put_u8(*a);
+                data.put_u8(*b);
             }
-            Self::Other(a, _) => {
-                data.extend_from_slice(a.to_u8_array().as_slice())
+            Self::OEMDeviceId(a) => {
+                a.append_value_to_data(data);
             }
-        }
-    }
-    pub fn append_value_to_data(&self, data: &mut Vec<u8>) {
-        match self {
-            Self::IpAddr(a) => {
-                data.extend_from_slice(a.0.octets().as_slice());
-                data.extend_from_slice(a.1.octets().as_slice());
-                data.extend_from_slice(a.2.octets().as_slice());
+            Self::StandardGateway(a) => {
+                data.put_slice(a.to_u16().to_be_bytes().as_slice());
             }
-            // Self::FullIpSuite(_, _, _, _) => 16,
-            Self::ManufacturerSpecific(val) => {
-                data.extend_from_slice(val.as_ref());
+            Self::RSIProperties(a) => {
+                data.put_slice(a.to_be_bytes().as_slice());
             }
-            Self::NameOfStation(val) => {
-                data.extend_from_slice(val.as_ref());
+            Self::DHCP(a) => {
+                a.append_value_to_data(data);
             }
-            Self::DeviceId(a, b) => {
-                data.extend_from_slice(a.as_ref());
-                data.extend_from_slice(b.as_ref());
+            Self::DeviceId(a) => {
+                a.append_value_to_data(data);
             }
-            Self::DeviceRole(a, b) => {
-                data.push(*a);
-                data.push(*b);
+            Self::DeviceRole(a) => {
+                data.put_u8(a.bits);
+                data.put_u8(a.reserved);
             }
             Self::DeviceOptions(val) => {
                 for option in val {
-                    data.extend_from_slice(option.to_u8_array().as_slice());
+                    data.put_slice(option.to_u8_array().as_slice());
                 }
             }
+            Self::StartTransaction | Self::EndTransaction | Self::ResetFactory(_) => {}
+            Self::Signal(a) => {
+                data.put_slice(a.to_u8_array().as_slice());
+            }
             Self::Response(a) => {
                 a.append_value_to_data(data);
             }
+            Self::DeviceInitiative(a) => {
+                data.put_slice(a.0.to_be_bytes().as_slice());
+            }
             Self::All => {
             }
             Self::Other(_, a) => {
-                data.extend_from_slice(a.as_ref())
+                data.put_slice(a.as_ref())
             }
         }
     }
     // data的长度校验，应该等于求出来的值
     pub fn init_by_ty(ty: OptionAndSub, data: BytesWrap) -> Result<Self> {
         Ok(match ty {
+            OptionAndSub::MacAddr => {
+                let val = data.slice(0..=5)?;
+                let val = val.as_ref();
+                Self::MacAddr(MacAddr::new(val[0], val[1], val[2], val[3], val[4], val[5]))
+            }
             OptionAndSub::IpAddr => {
                 let val = data.slice(0..=11)?;
                 let val = val.as_ref();
@@ -262,23 +737,23 @@ impl OptionAndSubValue {
                     Ipv4Addr::new(val[8], val[9], val[10], val[11]),
                 ))
             }
+            OptionAndSub::FullIpSuite => Self::FullIpSuite(InnerFullIpSuite::new(data)?),
             OptionAndSub::ManufacturerSpecific => Self::ManufacturerSpecific(data),
             OptionAndSub::NameOfStation => Self::NameOfStation(data),
-            OptionAndSub::DeviceId => {
-                let val = data.slice(0..=3)?;
-                let val = val.as_ref();
-                Self::DeviceId([val[0], val[1]], [val[2], val[3]])
-            }
+            OptionAndSub::DeviceId => Self::DeviceId(DeviceId::try_from(data)?),
             OptionAndSub::DeviceRole => {
                 let val = data.slice(0..=1)?;
                 let val = val.as_ref();
-                Self::DeviceRole(val[0], val[1])
+                Self::DeviceRole(DeviceRole {
+                    bits: val[0],
+                    reserved: val[1],
+                })
             }
             OptionAndSub::DeviceOptions => {
                 let mut index = 0;
                 let mut options = Vec::new();
                 while let Ok(val) = data.slice(index..) {
-                    if val.len() == 0 {
+                    if val.is_empty() {
                         break;
                     }
                     options.push(OptionAndSub::try_from(val)?);
@@ -286,10 +761,44 @@ impl OptionAndSubValue {
                 }
                 Self::DeviceOptions(options)
             }
+            OptionAndSub::AliasName => Self::AliasName(data),
+            OptionAndSub::DeviceInstance => {
+                let val = data.slice(0..=1)?;
+                let val = val.as_ref();
+                Self::DeviceInstance(val[0], val[1])
+            }
+            OptionAndSub::OEMDeviceId => Self::OEMDeviceId(DeviceId::try_from(data)?),
+            OptionAndSub::StandardGateway => {
+                let val = data.slice(0..=1)?;
+                let val = val.as_ref();
+                Self::StandardGateway(u16::from_be_bytes([val[0], val[1]]).into())
+            }
+            OptionAndSub::RSIProperties => {
+                let val = data.slice(0..=1)?;
+                let val = val.as_ref();
+                Self::RSIProperties(u16::from_be_bytes([val[0], val[1]]))
+            }
+            OptionAndSub::DHCP(sub @ (DHCP_HOST_NAME | DHCP_CLIENT_ID | DHCP_CONTROL)) => {
+                Self::DHCP(DhcpValue::new(sub, data)?)
+            }
+            // other suboptions are typed when they carry the usual length prefix
+            OptionAndSub::DHCP(sub)
+                if data.as_ref().first().map(|len| *len as usize + 1) == Some(data.len()) =>
+            {
+                Self::DHCP(DhcpValue::new(sub, data)?)
+            }
+            OptionAndSub::StartTransaction => Self::StartTransaction,
+            OptionAndSub::EndTransaction => Self::EndTransaction,
+            OptionAndSub::Signal => Self::Signal(SignalValue::try_from(data)?),
             OptionAndSub::Response => {
                 let val = data.slice(0..=2)?;
                 Self::Response(Response::try_from(val)?)
             }
+            OptionAndSub::DeviceInitiative => {
+                let val = data.slice(0..=1)?;
+                let val = val.as_ref();
+                Self::DeviceInitiative(DeviceInitiative(u16::from_be_bytes([val[0], val[1]])))
+            }
             OptionAndSub::All => {
                 Self::All
             }
@@ -300,19 +809,53 @@ impl OptionAndSubValue {
     }
     pub fn payload_size(&self) -> usize {
         match self {
+            Self::MacAddr(_) => 6,
             Self::IpAddr(_) => 12,
-            // Self::FullIpSuite(_, _, _, _) => 16,
+            Self::FullIpSuite(_) => 28,
             Self::ManufacturerSpecific(val) => val.len(),
             Self::NameOfStation(val) => val.len(),
-            Self::DeviceId(_, _) => 4,
-            Self::DeviceRole(_, _) => 2,
+            Self::DeviceId(_) => 4,
+            Self::DeviceRole(_) => 2,
             Self::DeviceOptions(val) => val.len() * 2,
+            Self::AliasName(val) => val.len(),
+            Self::DeviceInstance(_, _) => 2,
+            Self::OEMDeviceId(_) => 4,
+            Self::StandardGateway(_) => 2,
+            Self::RSIProperties(_) => 2,
+            Self::DHCP(a) => a.payload_size(),
+            Self::StartTransaction | Self::EndTransaction | Self::ResetFactory(_) => 0,
+            Self::Signal(_) => 2,
             Self::Response(_) => 3,
+            Self::DeviceInitiative(_) => 2,
             Self::All => 0,
             Self::Other(_, a) => a.len(),
         }
     }
 
+    // the BlockQualifier a control option is sent with by default, `None` for data options
+    pub fn control_qualifier(&self) -> Option<BlockQualifier> {
+        match self {
+            Self::StartTransaction | Self::EndTransaction | Self::Signal(_) => {
+                Some(BlockQualifier::UseTemporary)
+            }
+            Self::ResetFactory(mode) => Some(mode.to_qualifier()),
+            _ => None,
+        }
+    }
+    // a transaction may be ended temporary or permanent, e.g. engineering tools end it with
+    // SavePermanent, the other control options need their own qualifier
+    pub fn accepts_qualifier(&self, qualifier: &BlockQualifier) -> bool {
+        match self {
+            Self::StartTransaction | Self::EndTransaction => matches!(
+                qualifier,
+                BlockQualifier::UseTemporary | BlockQualifier::SavePermanent
+            ),
+            _ => self
+                .control_qualifier()
+                .is_none_or(|expect| expect == *qualifier),
+        }
+    }
+
     pub fn append_to_ident_resp_default(self, packet: &mut PacketIdentResp) {
         packet.append_block_common_default(self);
     }
@@ -323,7 +866,7 @@ impl OptionAndSubValue {
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum OptionAndSub {
-    MarAddr,
+    MacAddr,
     IpAddr,
     FullIpSuite,
     ManufacturerSpecific,
@@ -332,12 +875,16 @@ pub enum OptionAndSub {
     DeviceRole,
     DeviceOptions,
     AliasName,
+    DeviceInstance,
+    OEMDeviceId,
+    StandardGateway,
+    RSIProperties,
     StartTransaction,
     EndTransaction,
     Signal,
     Response,
     ResetFactory,
-    DevicecInitiative,
+    DeviceInitiative,
     All,
     DHCP(u8),
     LLDP(u8),
@@ -345,22 +892,34 @@ pub enum OptionAndSub {
 }
 
 impl TryFrom<BytesWrap> for OptionAndSub {
-    type Error = anyhow::Error;
+    type Error = DcpError;
 
     fn try_from(value: BytesWrap) -> std::result::Result<Self, Self::Error> {
         if let Some(a) = value.as_ref().get(0..=1) {
             OptionAndSub::new(a[0], a[1])
         } else {
-            bail!("todo: {:?}", value);
+            Err(DcpError::Truncated {
+                needed: 2,
+                got: value.len(),
+            })
         }
     }
 }
 
 impl OptionAndSub {
+    // the old names are associated consts now: `OptionAndSub::MarAddr` still matches and
+    // compares, but `use OptionAndSub::MarAddr` no longer compiles
+    #[deprecated(note = "misspelled, use `OptionAndSub::MacAddr`")]
+    #[allow(non_upper_case_globals)]
+    pub const MarAddr: OptionAndSub = OptionAndSub::MacAddr;
+    #[deprecated(note = "misspelled, use `OptionAndSub::DeviceInitiative`")]
+    #[allow(non_upper_case_globals)]
+    pub const DevicecInitiative: OptionAndSub = OptionAndSub::DeviceInitiative;
+
     pub fn new(b: u8, c: u8) -> Result<Self> {
         let a = (b, c);
         Ok(match a {
-            (1, 1) => Self::MarAddr,
+            (1, 1) => Self::MacAddr,
             (1, 2) => Self::IpAddr,
             (1, 3) => Self::FullIpSuite,
             (2, 1) => Self::ManufacturerSpecific,
@@ -369,12 +928,16 @@ impl OptionAndSub {
             (2, 4) => Self::DeviceRole,
             (2, 5) => Self::DeviceOptions,
             (2, 6) => Self::AliasName,
+            (2, 7) => Self::DeviceInstance,
+            (2, 8) => Self::OEMDeviceId,
+            (2, 9) => Self::StandardGateway,
+            (2, 10) => Self::RSIProperties,
             (5, 1) => Self::StartTransaction,
             (5, 2) => Self::EndTransaction,
             (5, 3) => Self::Signal,
             (5, 4) => Self::Response,
             (5, 6) => Self::ResetFactory,
-            (6, 1) => Self::DevicecInitiative,
+            (6, 1) => Self::DeviceInitiative,
             (255, 255) => Self::All,
             (3, a) => Self::DHCP(a),
             (4, a) => Self::LLDP(a),
@@ -383,7 +946,7 @@ impl OptionAndSub {
     }
     pub fn to_u8s(&self) -> (u8, u8) {
         match *self {
-            Self::MarAddr => (1, 1),
+            Self::MacAddr => (1, 1),
             Self::IpAddr => (1, 2),
             Self::FullIpSuite => (1, 3),
             Self::ManufacturerSpecific => (2, 1),
@@ -392,12 +955,16 @@ impl OptionAndSub {
             Self::DeviceRole => (2, 4),
             Self::DeviceOptions => (2, 5),
             Self::AliasName => (2, 6),
+            Self::DeviceInstance => (2, 7),
+            Self::OEMDeviceId => (2, 8),
+            Self::StandardGateway => (2, 9),
+            Self::RSIProperties => (2, 10),
             Self::StartTransaction => (5, 1),
             Self::EndTransaction => (5, 2),
             Self::Signal => (5, 3),
             Self::Response => (5, 4),
             Self::ResetFactory => (5, 6),
-            Self::DevicecInitiative => (6, 1),
+            Self::DeviceInitiative => (6, 1),
             Self::All => (255, 255),
             Self::DHCP(a) => (3, a),
             Self::LLDP(a) => (4, a),
@@ -407,7 +974,7 @@ impl OptionAndSub {
     #[inline]
     pub fn to_u8_array(&self) -> [u8; 2] {
         match *self {
-            Self::MarAddr => [1, 1],
+            Self::MacAddr => [1, 1],
             Self::IpAddr => [1, 2],
             Self::FullIpSuite => [1, 3],
             Self::ManufacturerSpecific => [2, 1],
@@ -416,12 +983,16 @@ impl OptionAndSub {
             Self::DeviceRole => [2, 4],
             Self::DeviceOptions => [2, 5],
             Self::AliasName => [2, 6],
+            Self::DeviceInstance => [2, 7],
+            Self::OEMDeviceId => [2, 8],
+            Self::StandardGateway => [2, 9],
+            Self::RSIProperties => [2, 10],
             Self::StartTransaction => [5, 1],
             Self::EndTransaction => [5, 2],
             Self::Signal => [5, 3],
             Self::Response => [5, 4],
             Self::ResetFactory => [5, 6],
-            Self::DevicecInitiative => [6, 1],
+            Self::DeviceInitiative => [6, 1],
             Self::All => [255, 255],
             Self::DHCP(a) => [3, a],
             Self::LLDP(a) => [4, a],
@@ -442,7 +1013,7 @@ pub enum BlockError {
 }
 
 impl TryFrom<u8> for BlockError {
-    type Error = anyhow::Error;
+    type Error = DcpError;
 
     fn try_from(value: u8) -> std::result::Result<Self, Self::Error> {
         Ok(match value {
@@ -453,14 +1024,15 @@ impl TryFrom<u8> for BlockError {
             0x04 => Self::ResourceError,
             0x05 => Self::SETNotPossibleByLocalReasons,
             0x06 => Self::InOoperationSETNotPossible,
-            a => bail!("unsupport block error: {}", a),
+            a => return Err(DcpError::UnknownBlockError(a)),
         })
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Default)]
 pub enum IpBlockInfo {
     IpNotSet,
+    #[default]
     IpSet,
     IpSetByDhcp,
     IpNotSetConflict,
@@ -469,12 +1041,6 @@ pub enum IpBlockInfo {
     UnSupport([u8; 2]),
 }
 
-impl Default for IpBlockInfo {
-    fn default() -> Self {
-        Self::IpSet
-    }
-}
-
 impl IpBlockInfo {
     pub fn to_u8_array(&self) -> [u8; 2] {
         match self {
@@ -484,13 +1050,13 @@ impl IpBlockInfo {
             Self::IpNotSetConflict => [0x00, 0x80],
             Self::IpSetConflict => [0x00, 0x81],
             Self::IpSetByDhcpConflict => [0x00, 0x82],
-            Self::UnSupport(data) => data.clone(),
+            Self::UnSupport(data) => *data,
         }
     }
 }
 
 impl TryFrom<BytesWrap> for IpBlockInfo {
-    type Error = anyhow::Error;
+    type Error = DcpError;
     fn try_from(value: BytesWrap) -> Result<Self, Self::Error> {
         let val = value.slice(0..=1)?;
         let data = [val.as_ref()[0], val.as_ref()[1]];
@@ -0,0 +1,212 @@
+use crate::comm::BytesWrap;
+use crate::error::DcpError;
+use crate::options::punycode;
+use crate::options::{OptionAndSub, OptionAndSubValue};
+use std::fmt::{Display, Formatter};
+use std::net::Ipv4Addr;
+use std::str::FromStr;
+
+// limits in octets
+const MAX_NAME_LEN: usize = 240;
+const MAX_LABEL_LEN: usize = 63;
+// PROFINET uses a single hyphen instead of the "xn--" ACE prefix of IDNA
+const ACE_PREFIX: &str = "xn-";
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum NameOfStationError {
+    Empty,
+    TooLong(usize),
+    EmptyLabel,
+    LabelTooLong { label: String, len: usize },
+    InvalidChar { ch: char, position: usize },
+    LeadingHyphen { label: String },
+    TrailingHyphen { label: String },
+    PortName,
+    IpAddress,
+    Punycode(String),
+}
+
+impl Display for NameOfStationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "name of station must not be empty"),
+            Self::TooLong(len) => write!(
+                f,
+                "name of station is {} bytes long, at most {} are allowed",
+                len, MAX_NAME_LEN
+            ),
+            Self::EmptyLabel => write!(f, "name of station contains an empty label"),
+            Self::LabelTooLong { label, len } => write!(
+                f,
+                "label \"{}\" is {} bytes long, at most {} are allowed",
+                label, len, MAX_LABEL_LEN
+            ),
+            Self::InvalidChar { ch, position } => write!(
+                f,
+                "invalid char {:?} at {}, only a-z, 0-9, '-' and '.' are allowed",
+                ch, position
+            ),
+            Self::LeadingHyphen { label } => {
+                write!(f, "label \"{}\" must not start with '-'", label)
+            }
+            Self::TrailingHyphen { label } => {
+                write!(f, "label \"{}\" must not end with '-'", label)
+            }
+            Self::PortName => write!(
+                f,
+                "name of station must not have the form \"port-xyz\" or \"port-xyz-abcde\""
+            ),
+            Self::IpAddress => write!(f, "name of station must not have the form n.n.n.n"),
+            Self::Punycode(label) => write!(f, "label \"{}\" can not be punycode encoded", label),
+        }
+    }
+}
+
+impl std::error::Error for NameOfStationError {}
+
+// NameOfStation that satisfies the PROFINET naming rules (DNS like labels, see IEC 61158-6-10)
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+pub struct NameOfStation(String);
+
+impl NameOfStation {
+    pub fn new(name: &str) -> Result<Self, NameOfStationError> {
+        Self::validate(name)?;
+        Ok(Self(name.to_string()))
+    }
+    pub fn validate(name: &str) -> Result<(), NameOfStationError> {
+        if name.is_empty() {
+            return Err(NameOfStationError::Empty);
+        }
+        if name.len() > MAX_NAME_LEN {
+            return Err(NameOfStationError::TooLong(name.len()));
+        }
+        if let Some((position, ch)) = name
+            .char_indices()
+            .find(|(_, ch)| !matches!(ch, 'a'..='z' | '0'..='9' | '-' | '.'))
+        {
+            return Err(NameOfStationError::InvalidChar { ch, position });
+        }
+        for label in name.split('.') {
+            if label.is_empty() {
+                return Err(NameOfStationError::EmptyLabel);
+            }
+            if label.len() > MAX_LABEL_LEN {
+                return Err(NameOfStationError::LabelTooLong {
+                    label: label.to_string(),
+                    len: label.len(),
+                });
+            }
+            if label.starts_with('-') {
+                return Err(NameOfStationError::LeadingHyphen {
+                    label: label.to_string(),
+                });
+            }
+            if label.ends_with('-') {
+                return Err(NameOfStationError::TrailingHyphen {
+                    label: label.to_string(),
+                });
+            }
+        }
+        if is_port_name(name.split('.').next().unwrap_or_default()) {
+            return Err(NameOfStationError::PortName);
+        }
+        if name.parse::<Ipv4Addr>().is_ok() || is_ip_form(name) {
+            return Err(NameOfStationError::IpAddress);
+        }
+        Ok(())
+    }
+    // encode a human-readable name, labels with non ascii chars are converted to "xn-" punycode
+    pub fn from_unicode(name: &str) -> Result<Self, NameOfStationError> {
+        let labels = name
+            .to_lowercase()
+            .split('.')
+            .map(|label| {
+                if label.is_ascii() {
+                    Ok(label.to_string())
+                } else {
+                    punycode::encode(label)
+                        .map(|x| format!("{}{}", ACE_PREFIX, x))
+                        .ok_or_else(|| NameOfStationError::Punycode(label.to_string()))
+                }
+            })
+            .collect::<Result<Vec<String>, NameOfStationError>>()?;
+        Self::new(&labels.join("."))
+    }
+    // decode the "xn-" labels, "xn-" is also an ordinary ascii prefix, so a label is only
+    // decoded when from_unicode would encode the result back to the same label
+    pub fn to_unicode(&self) -> String {
+        self.0
+            .split('.')
+            .map(|label| {
+                label
+                    .strip_prefix(ACE_PREFIX)
+                    .and_then(|encoded| {
+                        let decoded = punycode::decode(encoded)?;
+                        let again = punycode::encode(&decoded.to_lowercase())?;
+                        (!decoded.is_ascii() && again == encoded).then_some(decoded)
+                    })
+                    .unwrap_or_else(|| label.to_string())
+            })
+            .collect::<Vec<String>>()
+            .join(".")
+    }
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+    pub fn to_option(&self) -> OptionAndSubValue {
+        OptionAndSubValue::NameOfStation(self.0.as_bytes().into())
+    }
+}
+
+// "port-xyz" or "port-xyz-abcde" with x, y, z, a, b, c, d, e = 0..9
+fn is_port_name(label: &str) -> bool {
+    let is_digits = |a: &str, len: usize| a.len() == len && a.bytes().all(|x| x.is_ascii_digit());
+    match label.strip_prefix("port-") {
+        Some(rest) => match rest.split_once('-') {
+            Some((port, slot)) => is_digits(port, 3) && is_digits(slot, 5),
+            None => is_digits(rest, 3),
+        },
+        None => false,
+    }
+}
+
+// n.n.n.n with n = 0..999
+fn is_ip_form(name: &str) -> bool {
+    let labels: Vec<&str> = name.split('.').collect();
+    labels.len() == 4
+        && labels
+            .iter()
+            .all(|x| (1..=3).contains(&x.len()) && x.bytes().all(|x| x.is_ascii_digit()))
+}
+
+impl Display for NameOfStation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for NameOfStation {
+    type Err = NameOfStationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<BytesWrap> for NameOfStation {
+    type Error = DcpError;
+
+    fn try_from(value: BytesWrap) -> Result<Self, Self::Error> {
+        let name = std::str::from_utf8(value.as_ref()).map_err(|_| DcpError::InvalidValue {
+            option: OptionAndSub::NameOfStation,
+            value: value.as_ref().to_vec(),
+        })?;
+        Ok(Self::new(name)?)
+    }
+}
+
+impl AsRef<str> for NameOfStation {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
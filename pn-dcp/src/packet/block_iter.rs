@@ -0,0 +1,153 @@
+use crate::block::{
+    BlockCommon, BlockCommonWithoutInfo, BlockFullIpSuite, BlockIp, BlockResp, BlockSet,
+};
+use crate::options::{
+    DeviceId, DeviceInitiative, DeviceRole, InnerFullIpSuite, InnerIpAddr, NameOfStation,
+    OptionAndSub, OptionAndSubValue,
+};
+use pnet::util::MacAddr;
+
+// Borrowed block of any packet type, padding is skipped
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum DcpBlockRef<'a> {
+    // get req only carries option and suboption
+    Option(OptionAndSub),
+    Request(&'a BlockCommonWithoutInfo),
+    Common(&'a BlockCommon),
+    Ip(&'a BlockIp),
+    FullIpSuite(&'a BlockFullIpSuite),
+    Set(&'a BlockSet),
+    Resp(&'a BlockResp),
+}
+
+impl<'a> DcpBlockRef<'a> {
+    pub fn option(&self) -> OptionAndSub {
+        match self {
+            Self::Option(a) => *a,
+            Self::Ip(_) => OptionAndSub::IpAddr,
+            Self::FullIpSuite(_) => OptionAndSub::FullIpSuite,
+            Self::Resp(_) => OptionAndSub::Response,
+            Self::Request(a) => a.0.option(),
+            Self::Common(a) => a.option.option(),
+            Self::Set(a) => a.option.option(),
+        }
+    }
+    // None for get req options, ip, full ip suite and response blocks
+    pub fn option_value(&self) -> Option<&'a OptionAndSubValue> {
+        match self {
+            Self::Request(a) => Some(&a.0),
+            Self::Common(a) => Some(&a.option),
+            Self::Set(a) => Some(&a.option),
+            _ => None,
+        }
+    }
+}
+
+// Typed value that can be looked up in the blocks of a packet
+pub trait FromBlock: Sized {
+    fn from_block(block: &DcpBlockRef<'_>) -> Option<Self>;
+}
+
+pub trait DcpBlocks {
+    fn iter_blocks(&self) -> DcpBlockIter<'_>;
+    fn find<T: FromBlock>(&self) -> Option<T> {
+        self.iter_blocks().find_map(|x| T::from_block(&x))
+    }
+    fn find_option(&self, option: OptionAndSub) -> Option<DcpBlockRef<'_>> {
+        self.iter_blocks().find(|x| x.option() == option)
+    }
+}
+
+pub struct DcpBlockIter<'a>(Box<dyn Iterator<Item = DcpBlockRef<'a>> + 'a>);
+
+impl<'a> DcpBlockIter<'a> {
+    pub(crate) fn new(iter: impl Iterator<Item = DcpBlockRef<'a>> + 'a) -> Self {
+        Self(Box::new(iter))
+    }
+}
+
+impl<'a> Iterator for DcpBlockIter<'a> {
+    type Item = DcpBlockRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+impl FromBlock for NameOfStation {
+    fn from_block(block: &DcpBlockRef<'_>) -> Option<Self> {
+        match block.option_value()? {
+            OptionAndSubValue::NameOfStation(a) => NameOfStation::try_from(a.clone()).ok(),
+            _ => None,
+        }
+    }
+}
+
+impl FromBlock for InnerIpAddr {
+    fn from_block(block: &DcpBlockRef<'_>) -> Option<Self> {
+        match block {
+            DcpBlockRef::Ip(a) => Some(a.ip.clone()),
+            block => match block.option_value()? {
+                OptionAndSubValue::IpAddr(a) => Some(a.clone()),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl FromBlock for InnerFullIpSuite {
+    fn from_block(block: &DcpBlockRef<'_>) -> Option<Self> {
+        match block {
+            DcpBlockRef::FullIpSuite(a) => Some(a.ip.clone()),
+            block => match block.option_value()? {
+                OptionAndSubValue::FullIpSuite(a) => Some(a.clone()),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl FromBlock for MacAddr {
+    fn from_block(block: &DcpBlockRef<'_>) -> Option<Self> {
+        match block.option_value()? {
+            OptionAndSubValue::MacAddr(a) => Some(*a),
+            _ => None,
+        }
+    }
+}
+
+impl FromBlock for DeviceId {
+    fn from_block(block: &DcpBlockRef<'_>) -> Option<Self> {
+        match block.option_value()? {
+            OptionAndSubValue::DeviceId(a) => Some(*a),
+            _ => None,
+        }
+    }
+}
+
+impl FromBlock for DeviceRole {
+    fn from_block(block: &DcpBlockRef<'_>) -> Option<Self> {
+        match block.option_value()? {
+            OptionAndSubValue::DeviceRole(a) => Some(*a),
+            _ => None,
+        }
+    }
+}
+
+impl FromBlock for DeviceInitiative {
+    fn from_block(block: &DcpBlockRef<'_>) -> Option<Self> {
+        match block.option_value()? {
+            OptionAndSubValue::DeviceInitiative(a) => Some(*a),
+            _ => None,
+        }
+    }
+}
+
+impl FromBlock for BlockResp {
+    fn from_block(block: &DcpBlockRef<'_>) -> Option<Self> {
+        match block {
+            DcpBlockRef::Resp(a) => Some((*a).clone()),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use crate::error::{DcpError, Result};
+use crate::packet::block_iter::{DcpBlockIter, DcpBlocks};
+use crate::packet::get_req::PacketGetReq;
+use crate::packet::get_resp::PacketGetResp;
+use crate::packet::hello_req::PacketHelloReq;
+use crate::packet::hello_resp::PacketHelloResp;
+use crate::packet::ident_req::PacketIdentReq;
+use crate::packet::ident_resp::PacketIdentResp;
+use crate::packet::set_req::PacketSetReq;
+use crate::packet::set_resp::PacketSetResp;
+use crate::packet::{DcpEncode, DcpHead, DcpParse, PnDcp, PnDcpTy};
+use crate::parse::ParseContext;
+use bytes::BufMut;
+use pnet::util::MacAddr;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum DcpPacket {
+    HelloReq(PacketHelloReq),
+    HelloResp(PacketHelloResp),
+    IdentReq(PacketIdentReq),
+    IdentResp(PacketIdentResp),
+    GetReq(PacketGetReq),
+    GetResp(PacketGetResp),
+    SetReq(PacketSetReq),
+    SetResp(PacketSetResp),
+}
+
+impl DcpPacket {
+    pub fn head(&self) -> &DcpHead {
+        match self {
+            Self::HelloReq(a) => a,
+            Self::HelloResp(a) => a,
+            Self::IdentReq(a) => a,
+            Self::IdentResp(a) => a,
+            Self::GetReq(a) => a,
+            Self::GetResp(a) => a,
+            Self::SetReq(a) => a,
+            Self::SetResp(a) => a,
+        }
+    }
+    pub fn ty(&self) -> PnDcpTy {
+        self.head().ty
+    }
+    pub fn xid(&self) -> [u8; 4] {
+        self.head().xid
+    }
+    pub fn source(&self) -> MacAddr {
+        self.head().source
+    }
+    pub fn destination(&self) -> MacAddr {
+        self.head().destination
+    }
+    pub fn is_response(&self) -> bool {
+        self.ty().status().is_some()
+    }
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(self.encoded_len());
+        self.write_to(&mut data);
+        data
+    }
+}
+
+impl DcpEncode for DcpPacket {
+    fn encoded_len(&self) -> usize {
+        match self {
+            Self::HelloReq(a) => a.encoded_len(),
+            Self::HelloResp(a) => a.encoded_len(),
+            Self::IdentReq(a) => a.encoded_len(),
+            Self::IdentResp(a) => a.encoded_len(),
+            Self::GetReq(a) => a.encoded_len(),
+            Self::GetResp(a) => a.encoded_len(),
+            Self::SetReq(a) => a.encoded_len(),
+            Self::SetResp(a) => a.encoded_len(),
+        }
+    }
+
+    fn write_to(&self, buf: &mut impl BufMut) {
+        match self {
+            Self::HelloReq(a) => a.write_to(buf),
+            Self::HelloResp(a) => a.write_to(buf),
+            Self::IdentReq(a) => a.write_to(buf),
+            Self::IdentResp(a) => a.write_to(buf),
+            Self::GetReq(a) => a.write_to(buf),
+            Self::GetResp(a) => a.write_to(buf),
+            Self::SetReq(a) => a.write_to(buf),
+            Self::SetResp(a) => a.write_to(buf),
+        }
+    }
+}
+
+impl TryFrom<PnDcp> for DcpPacket {
+    type Error = DcpError;
+
+    fn try_from(dcp: PnDcp) -> Result<Self, Self::Error> {
+        Self::parse_pn_dcp(dcp, &mut ParseContext::default())
+    }
+}
+
+impl DcpParse for DcpPacket {
+    fn parse_pn_dcp(dcp: PnDcp, ctx: &mut ParseContext) -> Result<Self> {
+        Ok(match dcp.head.ty {
+            PnDcpTy::HelloReq => Self::HelloReq(DcpParse::parse_pn_dcp(dcp, ctx)?),
+            PnDcpTy::HelloRespSuc | PnDcpTy::HelloRespUnsup => {
+                Self::HelloResp(DcpParse::parse_pn_dcp(dcp, ctx)?)
+            }
+            PnDcpTy::IdentReq => Self::IdentReq(DcpParse::parse_pn_dcp(dcp, ctx)?),
+            PnDcpTy::IdentRespSuc | PnDcpTy::IdentRespUnsup => {
+                Self::IdentResp(DcpParse::parse_pn_dcp(dcp, ctx)?)
+            }
+            PnDcpTy::GetReq => Self::GetReq(DcpParse::parse_pn_dcp(dcp, ctx)?),
+            PnDcpTy::GetRespSuc | PnDcpTy::GetRespUnsup => {
+                Self::GetResp(DcpParse::parse_pn_dcp(dcp, ctx)?)
+            }
+            PnDcpTy::SetReq => Self::SetReq(DcpParse::parse_pn_dcp(dcp, ctx)?),
+            PnDcpTy::SetRespSuc | PnDcpTy::SetRespUnsup => {
+                Self::SetResp(DcpParse::parse_pn_dcp(dcp, ctx)?)
+            }
+        })
+    }
+}
+
+impl TryFrom<&[u8]> for DcpPacket {
+    type Error = DcpError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        let dcp = PnDcp::try_from(value)?;
+        Self::try_from(dcp)
+    }
+}
+
+impl DcpBlocks for DcpPacket {
+    fn iter_blocks(&self) -> DcpBlockIter<'_> {
+        match self {
+            Self::HelloReq(a) => a.iter_blocks(),
+            Self::HelloResp(a) => a.iter_blocks(),
+            Self::IdentReq(a) => a.iter_blocks(),
+            Self::IdentResp(a) => a.iter_blocks(),
+            Self::GetReq(a) => a.iter_blocks(),
+            Self::GetResp(a) => a.iter_blocks(),
+            Self::SetReq(a) => a.iter_blocks(),
+            Self::SetResp(a) => a.iter_blocks(),
+        }
+    }
+}
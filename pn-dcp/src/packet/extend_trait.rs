@@ -1,12 +1,18 @@
 use crate::block::{
-    BlockCommon, BlockCommonWithoutInfo, BlockIp, BlockPadding, BlockResp, BlockSet,
+    BlockCommon, BlockCommonWithoutInfo, BlockFullIpSuite, BlockIp, BlockPadding, BlockResp,
+    BlockSet,
 };
 use crate::options::OptionAndSubValue;
+use crate::packet::dcp_packet::DcpPacket;
+use crate::packet::get_req::PacketGetReq;
+use crate::packet::get_resp::PacketGetResp;
 use crate::packet::get_resp::{GetRespBlock, GetRespBlocks};
-use crate::packet::ident_req::{IdentReqBlock, IdentReqBlocks};
-use crate::packet::ident_resp::{IdentRespBlock, IdentRespBlocks};
-use crate::packet::set_req::{SetReqBlock, SetReqBlocks};
-use crate::packet::set_resp::{SetRespBlock, SetRespBlocks};
+use crate::packet::hello_req::{HelloBlock, HelloBlocks, PacketHelloReq};
+use crate::packet::hello_resp::PacketHelloResp;
+use crate::packet::ident_req::{IdentReqBlock, IdentReqBlocks, PacketIdentReq};
+use crate::packet::ident_resp::{IdentRespBlock, IdentRespBlocks, PacketIdentResp};
+use crate::packet::set_req::{PacketSetReq, SetReqBlock, SetReqBlocks};
+use crate::packet::set_resp::{PacketSetResp, SetRespBlock, SetRespBlocks};
 
 impl From<BlockCommon> for GetRespBlock {
     fn from(a: BlockCommon) -> Self {
@@ -18,6 +24,11 @@ impl From<BlockIp> for GetRespBlock {
         Self::BlockIp(a)
     }
 }
+impl From<BlockFullIpSuite> for GetRespBlock {
+    fn from(a: BlockFullIpSuite) -> Self {
+        Self::BlockFullIpSuite(a)
+    }
+}
 impl From<BlockPadding> for GetRespBlock {
     fn from(a: BlockPadding) -> Self {
         Self::Padding(a)
@@ -34,6 +45,32 @@ impl From<Vec<GetRespBlock>> for GetRespBlocks {
     }
 }
 
+impl From<BlockCommon> for HelloBlock {
+    fn from(a: BlockCommon) -> Self {
+        Self::Block(a)
+    }
+}
+impl From<BlockIp> for HelloBlock {
+    fn from(a: BlockIp) -> Self {
+        Self::BlockIp(a)
+    }
+}
+impl From<BlockResp> for HelloBlock {
+    fn from(a: BlockResp) -> Self {
+        Self::BlockResp(a)
+    }
+}
+impl From<BlockPadding> for HelloBlock {
+    fn from(a: BlockPadding) -> Self {
+        Self::Padding(a)
+    }
+}
+impl From<Vec<HelloBlock>> for HelloBlocks {
+    fn from(val: Vec<HelloBlock>) -> Self {
+        Self(val)
+    }
+}
+
 impl From<BlockCommonWithoutInfo> for IdentReqBlock {
     fn from(a: BlockCommonWithoutInfo) -> Self {
         Self::Block(a)
@@ -65,6 +102,11 @@ impl From<BlockIp> for IdentRespBlock {
         Self::BlockIp(a)
     }
 }
+impl From<BlockFullIpSuite> for IdentRespBlock {
+    fn from(a: BlockFullIpSuite) -> Self {
+        Self::BlockFullIpSuite(a)
+    }
+}
 impl From<BlockPadding> for IdentRespBlock {
     fn from(a: BlockPadding) -> Self {
         Self::Padding(a)
@@ -107,3 +149,44 @@ impl From<Vec<SetReqBlock>> for SetReqBlocks {
         Self(val)
     }
 }
+
+impl From<PacketHelloReq> for DcpPacket {
+    fn from(a: PacketHelloReq) -> Self {
+        Self::HelloReq(a)
+    }
+}
+impl From<PacketHelloResp> for DcpPacket {
+    fn from(a: PacketHelloResp) -> Self {
+        Self::HelloResp(a)
+    }
+}
+impl From<PacketIdentReq> for DcpPacket {
+    fn from(a: PacketIdentReq) -> Self {
+        Self::IdentReq(a)
+    }
+}
+impl From<PacketIdentResp> for DcpPacket {
+    fn from(a: PacketIdentResp) -> Self {
+        Self::IdentResp(a)
+    }
+}
+impl From<PacketGetReq> for DcpPacket {
+    fn from(a: PacketGetReq) -> Self {
+        Self::GetReq(a)
+    }
+}
+impl From<PacketGetResp> for DcpPacket {
+    fn from(a: PacketGetResp) -> Self {
+        Self::GetResp(a)
+    }
+}
+impl From<PacketSetReq> for DcpPacket {
+    fn from(a: PacketSetReq) -> Self {
+        Self::SetReq(a)
+    }
+}
+impl From<PacketSetResp> for DcpPacket {
+    fn from(a: PacketSetResp) -> Self {
+        Self::SetResp(a)
+    }
+}
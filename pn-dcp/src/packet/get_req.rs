@@ -1,8 +1,11 @@
 use crate::block::{BlockOptionAndSub, BlockTrait};
 use crate::comm::BytesWrap;
+use crate::error::{DcpError, Result};
 use crate::options::OptionAndSub;
-use crate::packet::{DcpHead, PnDcp, PnDcpTy};
-use anyhow::bail;
+use crate::packet::block_iter::{DcpBlockIter, DcpBlockRef, DcpBlocks};
+use crate::packet::{DcpEncode, DcpHead, DcpParse, PnDcp, PnDcpTy};
+use crate::parse::{ParseContext, ParseWarning};
+use bytes::BufMut;
 use pn_dcp_macro::derefmut;
 use pnet::util::MacAddr;
 use std::ops::{Deref, DerefMut};
@@ -37,33 +40,42 @@ impl PacketGetReq {
         let mut options = Vec::new();
         let BlockGetReq(datas) = &self.blocks;
         for block in datas.iter() {
-            options.push(block.0.clone());
+            options.push(block.0);
         }
         options
     }
     pub fn to_vec(&self) -> Vec<u8> {
-        let mut data = Vec::with_capacity(self.head.payload_len + 26);
-        self.head.append_data(&mut data);
-        self.blocks.append_data(&mut data);
+        let mut data = Vec::with_capacity(self.encoded_len());
+        self.write_to(&mut data);
         data
     }
 }
 
 impl TryFrom<PnDcp> for PacketGetReq {
-    type Error = anyhow::Error;
+    type Error = DcpError;
 
     fn try_from(dcg: PnDcp) -> Result<Self, Self::Error> {
-        let PnDcp { head, blocks } = dcg;
+        Self::parse_pn_dcp(dcg, &mut ParseContext::default())
+    }
+}
+
+impl DcpParse for PacketGetReq {
+    fn parse_pn_dcp(dcg: PnDcp, ctx: &mut ParseContext) -> Result<Self> {
+        let PnDcp { mut head, blocks } = dcg;
         if head.ty != PnDcpTy::GetReq {
-            bail!("the packet is pn-dcp, but not get req!");
+            return Err(DcpError::UnexpectedServiceType {
+                expected: PnDcpTy::GetReq,
+                got: head.ty,
+            });
         }
-        let blocks = BlockGetReq::try_from(blocks)?;
+        let blocks = BlockGetReq::parse(blocks, ctx)?;
+        head.set_payload_len(blocks.len());
         Ok(Self { blocks, head })
     }
 }
 
 impl TryFrom<&[u8]> for PacketGetReq {
-    type Error = anyhow::Error;
+    type Error = DcpError;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
         let dcg = PnDcp::try_from(value)?;
@@ -88,7 +100,7 @@ impl BlockTrait for BlockGetReq {
         unreachable!()
     }
 
-    fn append_data(&self, data: &mut Vec<u8>) {
+    fn append_data(&self, data: &mut impl BufMut) {
         for block in &self.0 {
             block.append_data(data)
         }
@@ -102,18 +114,56 @@ impl From<Vec<BlockOptionAndSub>> for BlockGetReq {
 }
 
 impl TryFrom<BytesWrap> for BlockGetReq {
-    type Error = anyhow::Error;
+    type Error = DcpError;
     fn try_from(value: BytesWrap) -> Result<Self, Self::Error> {
+        Self::parse(value, &mut ParseContext::default())
+    }
+}
+
+impl BlockGetReq {
+    pub(crate) fn parse(value: BytesWrap, ctx: &mut ParseContext) -> Result<Self> {
         let mut index = 0usize;
         let mut blocks = Vec::<BlockOptionAndSub>::new();
         while let Ok(tmp) = value.slice(index..) {
-            if tmp.len() == 0 {
+            if tmp.is_empty() {
+                break;
+            }
+            // an odd payload ends with a padding byte
+            if tmp.len() == 1 {
+                ctx.check_padding(&value, index)?;
                 break;
             }
             let one = OptionAndSub::try_from(tmp)?;
-            blocks.push(one.into());
+            match one {
+                // control options can not be read
+                OptionAndSub::StartTransaction
+                | OptionAndSub::EndTransaction
+                | OptionAndSub::Signal
+                | OptionAndSub::Response
+                | OptionAndSub::ResetFactory
+                | OptionAndSub::All => ctx.report(ParseWarning::UnexpectedBlock(one))?,
+                _ => blocks.push(one.into()),
+            }
             index += 2;
         }
         Ok(blocks.into())
     }
 }
+
+impl DcpBlocks for PacketGetReq {
+    fn iter_blocks(&self) -> DcpBlockIter<'_> {
+        DcpBlockIter::new(self.blocks.0.iter().map(|x| DcpBlockRef::Option(x.0)))
+    }
+}
+
+impl DcpEncode for PacketGetReq {
+    fn encoded_len(&self) -> usize {
+        self.head.frame_len()
+    }
+
+    fn write_to(&self, buf: &mut impl BufMut) {
+        self.head.append_data(buf);
+        self.blocks.append_data(buf);
+        self.head.append_padding(buf);
+    }
+}
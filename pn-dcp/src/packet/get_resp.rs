@@ -1,9 +1,16 @@
-use crate::block::{BlockCommon, BlockIp, BlockPadding, BlockResp, BlockTrait};
+use crate::block::{
+    block_len, BlockCommon, BlockFullIpSuite, BlockIp, BlockPadding, BlockResp, BlockTrait,
+};
 use crate::comm::BytesWrap;
+use crate::error::{DcpError, Result};
 use crate::options::IpBlockInfo;
-use crate::options::{BlockError, BlockInfo, InnerIpAddr, OptionAndSub, OptionAndSubValue};
-use crate::packet::{DcpHead, PnDcp, PnDcpTy};
-use anyhow::{bail, Result};
+use crate::options::{
+    BlockError, BlockInfo, InnerFullIpSuite, InnerIpAddr, OptionAndSub, OptionAndSubValue,
+};
+use crate::packet::block_iter::{DcpBlockIter, DcpBlockRef, DcpBlocks};
+use crate::packet::{DcpEncode, DcpHead, DcpParse, PnDcp, PnDcpTy};
+use crate::parse::ParseContext;
+use bytes::BufMut;
 use pn_dcp_macro::derefmut;
 use pnet::util::MacAddr;
 use std::ops::{Deref, DerefMut};
@@ -44,6 +51,9 @@ impl PacketGetResp {
     pub fn append_block_ip(&mut self, ip: InnerIpAddr, info: IpBlockInfo) {
         self.append_block(BlockIp { ip, info })
     }
+    pub fn append_block_full_ip_suite(&mut self, ip: InnerFullIpSuite, info: IpBlockInfo) {
+        self.append_block(BlockFullIpSuite { ip, info })
+    }
     pub fn append_block_common(&mut self, option: OptionAndSubValue, info: BlockInfo) {
         self.append_block(BlockCommon { option, info })
     }
@@ -56,7 +66,15 @@ impl PacketGetResp {
                 return Ok(ip.clone());
             }
         }
-        bail!("not contain ip info!");
+        Err(DcpError::MissingBlock(OptionAndSub::IpAddr))
+    }
+    pub fn block_full_ip_suite(&self) -> Result<BlockFullIpSuite> {
+        for block in self.blocks.iter() {
+            if let GetRespBlock::BlockFullIpSuite(ip) = block {
+                return Ok(ip.clone());
+            }
+        }
+        Err(DcpError::MissingBlock(OptionAndSub::FullIpSuite))
     }
     pub fn block_commons(&self) -> Vec<BlockCommon> {
         let mut blocks = Vec::new();
@@ -78,27 +96,32 @@ impl PacketGetResp {
     }
 
     pub fn to_vec(&self) -> Vec<u8> {
-        let mut data = Vec::with_capacity(self.head.payload_len + 26);
-        self.head.append_data(&mut data);
-        self.blocks.append_data(&mut data);
+        let mut data = Vec::with_capacity(self.encoded_len());
+        self.write_to(&mut data);
         data
     }
 }
 
 impl TryFrom<PnDcp> for PacketGetResp {
-    type Error = anyhow::Error;
+    type Error = DcpError;
+
     fn try_from(dcg: PnDcp) -> Result<Self, Self::Error> {
-        let PnDcp { head, blocks } = dcg;
-        if head.ty != PnDcpTy::GetRespSuc {
-            bail!("the packet is pn-dcp, but not get resp success!");
-        }
-        let blocks = GetRespBlocks::try_from(blocks)?;
+        Self::parse_pn_dcp(dcg, &mut ParseContext::default())
+    }
+}
+
+impl DcpParse for PacketGetResp {
+    fn parse_pn_dcp(dcg: PnDcp, ctx: &mut ParseContext) -> Result<Self> {
+        let PnDcp { mut head, blocks } = dcg;
+        head.ty.expect_response(PnDcpTy::GetRespSuc)?;
+        let blocks = GetRespBlocks::parse(blocks, ctx)?;
+        head.set_payload_len(blocks.len());
         Ok(Self { blocks, head })
     }
 }
 
 impl TryFrom<&[u8]> for PacketGetResp {
-    type Error = anyhow::Error;
+    type Error = DcpError;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
         let dcg = PnDcp::try_from(value)?;
@@ -110,6 +133,7 @@ impl TryFrom<&[u8]> for PacketGetResp {
 pub enum GetRespBlock {
     Block(BlockCommon),
     BlockIp(BlockIp),
+    BlockFullIpSuite(BlockFullIpSuite),
     BlockResp(BlockResp),
     Padding(BlockPadding),
 }
@@ -123,6 +147,7 @@ impl BlockTrait for GetRespBlock {
             Self::Block(a) => a.len(),
             Self::Padding(a) => a.len(),
             Self::BlockIp(a) => a.len(),
+            Self::BlockFullIpSuite(a) => a.len(),
             Self::BlockResp(a) => a.len(),
         }
     }
@@ -132,15 +157,17 @@ impl BlockTrait for GetRespBlock {
             Self::Block(a) => a.payload(),
             Self::Padding(a) => a.payload(),
             Self::BlockIp(a) => a.payload(),
+            Self::BlockFullIpSuite(a) => a.payload(),
             Self::BlockResp(a) => a.payload(),
         }
     }
 
-    fn append_data(&self, data: &mut Vec<u8>) {
+    fn append_data(&self, data: &mut impl BufMut) {
         match self {
             Self::Block(a) => a.append_data(data),
             Self::Padding(a) => a.append_data(data),
             Self::BlockIp(a) => a.append_data(data),
+            Self::BlockFullIpSuite(a) => a.append_data(data),
             Self::BlockResp(a) => a.append_data(data),
         }
     }
@@ -159,7 +186,7 @@ impl BlockTrait for GetRespBlocks {
         unreachable!()
     }
 
-    fn append_data(&self, data: &mut Vec<u8>) {
+    fn append_data(&self, data: &mut impl BufMut) {
         for block in &self.0 {
             block.append_data(data)
         }
@@ -167,37 +194,30 @@ impl BlockTrait for GetRespBlocks {
 }
 
 impl TryFrom<BytesWrap> for GetRespBlocks {
-    type Error = anyhow::Error;
+    type Error = DcpError;
     fn try_from(value: BytesWrap) -> Result<Self, Self::Error> {
+        Self::parse(value, &mut ParseContext::default())
+    }
+}
+
+impl GetRespBlocks {
+    pub(crate) fn parse(value: BytesWrap, ctx: &mut ParseContext) -> Result<Self> {
         let mut index = 0usize;
         let mut blocks = Vec::<GetRespBlock>::new();
         while let Ok(tmp) = value.slice(index..) {
-            if tmp.len() == 0 {
+            if tmp.is_empty() {
                 break;
             }
             let option = OptionAndSub::try_from(tmp.clone())?;
-            let len = match option {
-                OptionAndSub::IpAddr => {
-                    let block = BlockIp::try_from_bytes(tmp)?;
-                    // println!("{:?}", block);
-                    let len = block.len();
-                    blocks.push(block.into());
-                    len
-                }
-                OptionAndSub::Response => {
-                    let block = BlockResp::try_from(tmp)?;
-                    let len = block.len();
-                    blocks.push(block.into());
-                    len
-                }
-                option => {
-                    let block = BlockCommon::try_from_bytes(option, tmp)?;
-                    let len = block.len();
-                    blocks.push(block.into());
-                    len
-                }
-            };
+            let len = block_len(option, &tmp)?;
+            match option {
+                OptionAndSub::IpAddr => blocks.push(BlockIp::parse(tmp, ctx)?.into()),
+                OptionAndSub::FullIpSuite => blocks.push(BlockFullIpSuite::parse(tmp, ctx)?.into()),
+                OptionAndSub::Response => blocks.push(BlockResp::parse(tmp, ctx)?.into()),
+                option => blocks.push(BlockCommon::parse(option, tmp, ctx)?.into()),
+            }
             if len % 2 == 1 {
+                ctx.check_padding(&value, index + len)?;
                 blocks.push(BlockPadding.into());
                 index += 1;
             }
@@ -206,3 +226,33 @@ impl TryFrom<BytesWrap> for GetRespBlocks {
         Ok(blocks.into())
     }
 }
+
+impl GetRespBlock {
+    pub fn block_ref(&self) -> Option<DcpBlockRef<'_>> {
+        match self {
+            Self::Block(a) => Some(DcpBlockRef::Common(a)),
+            Self::BlockIp(a) => Some(DcpBlockRef::Ip(a)),
+            Self::BlockFullIpSuite(a) => Some(DcpBlockRef::FullIpSuite(a)),
+            Self::BlockResp(a) => Some(DcpBlockRef::Resp(a)),
+            Self::Padding(_) => None,
+        }
+    }
+}
+
+impl DcpBlocks for PacketGetResp {
+    fn iter_blocks(&self) -> DcpBlockIter<'_> {
+        DcpBlockIter::new(self.blocks.0.iter().filter_map(GetRespBlock::block_ref))
+    }
+}
+
+impl DcpEncode for PacketGetResp {
+    fn encoded_len(&self) -> usize {
+        self.head.frame_len()
+    }
+
+    fn write_to(&self, buf: &mut impl BufMut) {
+        self.head.append_data(buf);
+        self.blocks.append_data(buf);
+        self.head.append_padding(buf);
+    }
+}
@@ -0,0 +1,254 @@
+use crate::block::{block_len, BlockCommon, BlockIp, BlockPadding, BlockResp, BlockTrait};
+use crate::comm::BytesWrap;
+use crate::error::{DcpError, Result};
+use crate::options::IpBlockInfo;
+use crate::options::{BlockInfo, InnerIpAddr, OptionAndSub, OptionAndSubValue};
+use crate::packet::block_iter::{DcpBlockIter, DcpBlockRef, DcpBlocks};
+use crate::packet::{DcpEncode, DcpHead, DcpParse, PnDcp, PnDcpTy};
+use crate::parse::ParseContext;
+use bytes::BufMut;
+use pn_dcp_macro::derefmut;
+use pnet::util::MacAddr;
+use std::ops::{Deref, DerefMut};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum HelloBlock {
+    Block(BlockCommon),
+    BlockIp(BlockIp),
+    BlockResp(BlockResp),
+    Padding(BlockPadding),
+}
+
+#[derive(Debug, Eq, PartialEq, Default, Clone)]
+#[derefmut(0)]
+pub struct HelloBlocks(pub(crate) Vec<HelloBlock>);
+
+impl BlockTrait for HelloBlock {
+    fn len(&self) -> usize {
+        match self {
+            Self::Block(a) => a.len(),
+            Self::BlockIp(a) => a.len(),
+            Self::BlockResp(a) => a.len(),
+            Self::Padding(a) => a.len(),
+        }
+    }
+
+    fn payload(&self) -> u16 {
+        match self {
+            Self::Block(a) => a.payload(),
+            Self::BlockIp(a) => a.payload(),
+            Self::BlockResp(a) => a.payload(),
+            Self::Padding(a) => a.payload(),
+        }
+    }
+
+    fn append_data(&self, data: &mut impl BufMut) {
+        match self {
+            Self::Block(a) => a.append_data(data),
+            Self::BlockIp(a) => a.append_data(data),
+            Self::BlockResp(a) => a.append_data(data),
+            Self::Padding(a) => a.append_data(data),
+        }
+    }
+}
+
+impl BlockTrait for HelloBlocks {
+    fn len(&self) -> usize {
+        let mut len = 0;
+        for block in &self.0 {
+            len += block.len();
+        }
+        len
+    }
+
+    fn payload(&self) -> u16 {
+        unreachable!()
+    }
+
+    fn append_data(&self, data: &mut impl BufMut) {
+        for block in &self.0 {
+            block.append_data(data)
+        }
+    }
+}
+
+impl TryFrom<BytesWrap> for HelloBlocks {
+    type Error = DcpError;
+    fn try_from(value: BytesWrap) -> Result<Self, Self::Error> {
+        Self::parse(value, &mut ParseContext::default())
+    }
+}
+
+impl HelloBlocks {
+    pub(crate) fn parse(value: BytesWrap, ctx: &mut ParseContext) -> Result<Self> {
+        let mut index = 0usize;
+        let mut blocks = Vec::<HelloBlock>::new();
+        while let Ok(tmp) = value.slice(index..) {
+            if tmp.is_empty() {
+                break;
+            }
+            let option = OptionAndSub::try_from(tmp.clone())?;
+            let len = block_len(option, &tmp)?;
+            match option {
+                OptionAndSub::IpAddr => blocks.push(BlockIp::parse(tmp, ctx)?.into()),
+                OptionAndSub::Response => blocks.push(BlockResp::parse(tmp, ctx)?.into()),
+                option => blocks.push(BlockCommon::parse(option, tmp, ctx)?.into()),
+            }
+            if len % 2 == 1 {
+                ctx.check_padding(&value, index + len)?;
+                blocks.push(BlockPadding.into());
+                index += 1;
+            }
+            index += len;
+        }
+        Ok(blocks.into())
+    }
+}
+
+impl HelloBlocks {
+    pub(crate) fn append_block(&mut self, head: &mut DcpHead, block: impl Into<HelloBlock>) {
+        let block = block.into();
+        let block_len = block.len();
+        self.0.push(block);
+        head.add_payload_len(block_len);
+        if block_len % 2 == 1 {
+            self.0.push(HelloBlock::Padding(BlockPadding));
+            head.add_payload_len(1);
+        }
+    }
+    pub fn block_ip(&self) -> Result<BlockIp> {
+        for block in self.iter() {
+            if let HelloBlock::BlockIp(ip) = block {
+                return Ok(ip.clone());
+            }
+        }
+        Err(DcpError::MissingBlock(OptionAndSub::IpAddr))
+    }
+    pub fn block_commons(&self) -> Vec<BlockCommon> {
+        let mut blocks = Vec::new();
+        for block in self.iter() {
+            if let HelloBlock::Block(common) = block {
+                blocks.push(common.clone());
+            }
+        }
+        blocks
+    }
+    pub fn block_resps(&self) -> Vec<BlockResp> {
+        let mut blocks = Vec::new();
+        for block in self.iter() {
+            if let HelloBlock::BlockResp(resp) = block {
+                blocks.push(resp.clone());
+            }
+        }
+        blocks
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[derefmut(head)]
+pub struct PacketHelloReq {
+    head: DcpHead,
+    blocks: HelloBlocks,
+}
+
+impl Deref for PacketHelloReq {
+    type Target = DcpHead;
+
+    fn deref(&self) -> &Self::Target {
+        &self.head
+    }
+}
+
+impl PacketHelloReq {
+    pub fn new(source: MacAddr) -> Self {
+        let destination = MacAddr::new(0x01, 0x0e, 0xcf, 0x00, 0x00, 0x01);
+        let head = DcpHead::new(destination, source, PnDcpTy::HelloReq);
+        Self {
+            head,
+            blocks: HelloBlocks::default(),
+        }
+    }
+    pub fn append_block_ip(&mut self, ip: InnerIpAddr, info: IpBlockInfo) {
+        self.blocks
+            .append_block(&mut self.head, BlockIp { ip, info })
+    }
+    pub fn append_block_common(&mut self, option: OptionAndSubValue, info: BlockInfo) {
+        self.blocks
+            .append_block(&mut self.head, BlockCommon { option, info })
+    }
+    pub fn append_block_common_default(&mut self, option: OptionAndSubValue) {
+        self.append_block_common(option, BlockInfo::Reserved)
+    }
+    pub fn block_ip(&self) -> Result<BlockIp> {
+        self.blocks.block_ip()
+    }
+    pub fn block_commons(&self) -> Vec<BlockCommon> {
+        self.blocks.block_commons()
+    }
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(self.encoded_len());
+        self.write_to(&mut data);
+        data
+    }
+}
+
+impl TryFrom<PnDcp> for PacketHelloReq {
+    type Error = DcpError;
+
+    fn try_from(dcg: PnDcp) -> Result<Self, Self::Error> {
+        Self::parse_pn_dcp(dcg, &mut ParseContext::default())
+    }
+}
+
+impl DcpParse for PacketHelloReq {
+    fn parse_pn_dcp(dcg: PnDcp, ctx: &mut ParseContext) -> Result<Self> {
+        let PnDcp { mut head, blocks } = dcg;
+        if head.ty != PnDcpTy::HelloReq {
+            return Err(DcpError::UnexpectedServiceType {
+                expected: PnDcpTy::HelloReq,
+                got: head.ty,
+            });
+        }
+        let blocks = HelloBlocks::parse(blocks, ctx)?;
+        head.set_payload_len(blocks.len());
+        Ok(Self { blocks, head })
+    }
+}
+
+impl TryFrom<&[u8]> for PacketHelloReq {
+    type Error = DcpError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        let dcg = PnDcp::try_from(value)?;
+        PacketHelloReq::try_from(dcg)
+    }
+}
+
+impl HelloBlock {
+    pub fn block_ref(&self) -> Option<DcpBlockRef<'_>> {
+        match self {
+            Self::Block(a) => Some(DcpBlockRef::Common(a)),
+            Self::BlockIp(a) => Some(DcpBlockRef::Ip(a)),
+            Self::BlockResp(a) => Some(DcpBlockRef::Resp(a)),
+            Self::Padding(_) => None,
+        }
+    }
+}
+
+impl DcpBlocks for PacketHelloReq {
+    fn iter_blocks(&self) -> DcpBlockIter<'_> {
+        DcpBlockIter::new(self.blocks.0.iter().filter_map(HelloBlock::block_ref))
+    }
+}
+
+impl DcpEncode for PacketHelloReq {
+    fn encoded_len(&self) -> usize {
+        self.head.frame_len()
+    }
+
+    fn write_to(&self, buf: &mut impl BufMut) {
+        self.head.append_data(buf);
+        self.blocks.append_data(buf);
+        self.head.append_padding(buf);
+    }
+}
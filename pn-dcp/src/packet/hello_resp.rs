@@ -0,0 +1,120 @@
+use crate::block::{BlockCommon, BlockIp, BlockResp, BlockTrait};
+use crate::error::{DcpError, Result};
+use crate::options::IpBlockInfo;
+use crate::options::{BlockError, BlockInfo, InnerIpAddr, OptionAndSub, OptionAndSubValue};
+use crate::packet::block_iter::{DcpBlockIter, DcpBlocks};
+use crate::packet::hello_req::{HelloBlock, HelloBlocks, PacketHelloReq};
+use crate::packet::{DcpEncode, DcpHead, DcpParse, PnDcp, PnDcpTy};
+use crate::parse::ParseContext;
+use bytes::BufMut;
+use pn_dcp_macro::derefmut;
+use pnet::util::MacAddr;
+use std::ops::{Deref, DerefMut};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[derefmut(head)]
+pub struct PacketHelloResp {
+    head: DcpHead,
+    blocks: HelloBlocks,
+}
+
+impl Deref for PacketHelloResp {
+    type Target = DcpHead;
+
+    fn deref(&self) -> &Self::Target {
+        &self.head
+    }
+}
+
+impl PacketHelloResp {
+    pub fn new(source: MacAddr, dest: MacAddr) -> Self {
+        let head = DcpHead::new(dest, source, PnDcpTy::HelloRespSuc);
+        Self {
+            head,
+            blocks: HelloBlocks::default(),
+        }
+    }
+    pub fn from_req(source: MacAddr, hello_req: &PacketHelloReq) -> Self {
+        let mut head = DcpHead::new(hello_req.source, source, PnDcpTy::HelloRespSuc);
+        head.set_xid(hello_req.xid);
+        head.set_vlan(hello_req.vlan);
+        Self {
+            head,
+            blocks: HelloBlocks::default(),
+        }
+    }
+    pub fn append_block_ip(&mut self, ip: InnerIpAddr, info: IpBlockInfo) {
+        self.blocks
+            .append_block(&mut self.head, BlockIp { ip, info })
+    }
+    pub fn append_block_common(&mut self, option: OptionAndSubValue, info: BlockInfo) {
+        self.blocks
+            .append_block(&mut self.head, BlockCommon { option, info })
+    }
+    pub fn append_block_common_default(&mut self, option: OptionAndSubValue) {
+        self.append_block_common(option, BlockInfo::Reserved)
+    }
+    pub fn append_block_resp(&mut self, option: OptionAndSub, error: BlockError) {
+        self.blocks
+            .append_block(&mut self.head, BlockResp(option, error))
+    }
+    pub fn block_ip(&self) -> Result<BlockIp> {
+        self.blocks.block_ip()
+    }
+    pub fn block_commons(&self) -> Vec<BlockCommon> {
+        self.blocks.block_commons()
+    }
+    pub fn block_resps(&self) -> Vec<BlockResp> {
+        self.blocks.block_resps()
+    }
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(self.encoded_len());
+        self.write_to(&mut data);
+        data
+    }
+}
+
+impl TryFrom<PnDcp> for PacketHelloResp {
+    type Error = DcpError;
+
+    fn try_from(dcg: PnDcp) -> Result<Self, Self::Error> {
+        Self::parse_pn_dcp(dcg, &mut ParseContext::default())
+    }
+}
+
+impl DcpParse for PacketHelloResp {
+    fn parse_pn_dcp(dcg: PnDcp, ctx: &mut ParseContext) -> Result<Self> {
+        let PnDcp { mut head, blocks } = dcg;
+        head.ty.expect_response(PnDcpTy::HelloRespSuc)?;
+        let blocks = HelloBlocks::parse(blocks, ctx)?;
+        head.set_payload_len(blocks.len());
+        Ok(Self { blocks, head })
+    }
+}
+
+impl TryFrom<&[u8]> for PacketHelloResp {
+    type Error = DcpError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        let dcg = PnDcp::try_from(value)?;
+        PacketHelloResp::try_from(dcg)
+    }
+}
+
+impl DcpBlocks for PacketHelloResp {
+    fn iter_blocks(&self) -> DcpBlockIter<'_> {
+        DcpBlockIter::new(self.blocks.0.iter().filter_map(HelloBlock::block_ref))
+    }
+}
+
+impl DcpEncode for PacketHelloResp {
+    fn encoded_len(&self) -> usize {
+        self.head.frame_len()
+    }
+
+    fn write_to(&self, buf: &mut impl BufMut) {
+        self.head.append_data(buf);
+        self.blocks.append_data(buf);
+        self.head.append_padding(buf);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::options::{DeviceId, DeviceRole, InnerIpAddr, NameOfStation, OptionAndSubValue};
+use crate::packet::block_iter::{DcpBlockRef, DcpBlocks};
+use crate::packet::ident_req::PacketIdentReq;
+
+// Filter blocks of an ident request, a device answers when it matches all of them
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct IdentFilter(Vec<OptionAndSubValue>);
+
+impl IdentFilter {
+    pub fn identify_all() -> Self {
+        Self(vec![OptionAndSubValue::All])
+    }
+    pub fn by_name_of_station(name: &NameOfStation) -> Self {
+        Self::default().and_name_of_station(name)
+    }
+    pub fn by_alias(alias: &str) -> Self {
+        Self::default().and_alias(alias)
+    }
+    pub fn by_device_id(device_id: DeviceId) -> Self {
+        Self::default().and_device_id(device_id)
+    }
+    pub fn by_role(role: DeviceRole) -> Self {
+        Self::default().and_role(role)
+    }
+    pub fn by_ip(ip: InnerIpAddr) -> Self {
+        Self::default().and_ip(ip)
+    }
+    pub fn by_device_instance(high: u8, low: u8) -> Self {
+        Self::default().and_device_instance(high, low)
+    }
+    pub fn and_name_of_station(self, name: &NameOfStation) -> Self {
+        self.and(name.to_option())
+    }
+    pub fn and_alias(self, alias: &str) -> Self {
+        self.and(OptionAndSubValue::AliasName(alias.as_bytes().into()))
+    }
+    pub fn and_device_id(self, device_id: DeviceId) -> Self {
+        self.and(OptionAndSubValue::DeviceId(device_id))
+    }
+    pub fn and_role(self, role: DeviceRole) -> Self {
+        self.and(OptionAndSubValue::DeviceRole(role))
+    }
+    pub fn and_ip(self, ip: InnerIpAddr) -> Self {
+        self.and(OptionAndSubValue::IpAddr(ip))
+    }
+    pub fn and_device_instance(self, high: u8, low: u8) -> Self {
+        self.and(OptionAndSubValue::DeviceInstance(high, low))
+    }
+    // a filter replaces `All` and an earlier filter of the same option
+    pub fn and(mut self, option: OptionAndSubValue) -> Self {
+        self.0
+            .retain(|x| *x != OptionAndSubValue::All && x.option() != option.option());
+        self.0.push(option);
+        self
+    }
+    pub fn options(&self) -> &[OptionAndSubValue] {
+        &self.0
+    }
+    pub fn is_identify_all(&self) -> bool {
+        self.0 == [OptionAndSubValue::All]
+    }
+    // `device` holds the blocks the device would answer with, e.g. its ident response
+    pub fn matches(&self, device: &impl DcpBlocks) -> bool {
+        self.0.iter().all(|filter| option_matches(filter, device))
+    }
+}
+
+impl From<&PacketIdentReq> for IdentFilter {
+    fn from(req: &PacketIdentReq) -> Self {
+        Self(req.options())
+    }
+}
+
+fn option_matches(filter: &OptionAndSubValue, device: &impl DcpBlocks) -> bool {
+    if *filter == OptionAndSubValue::All {
+        return true;
+    }
+    match device.find_option(filter.option()) {
+        Some(DcpBlockRef::Ip(block)) => *filter == OptionAndSubValue::IpAddr(block.ip().clone()),
+        Some(block) => block
+            .option_value()
+            .is_some_and(|value| value_matches(filter, value)),
+        None => false,
+    }
+}
+
+fn value_matches(filter: &OptionAndSubValue, value: &OptionAndSubValue) -> bool {
+    match (filter, value) {
+        // names are compared case-insensitive
+        (OptionAndSubValue::NameOfStation(a), OptionAndSubValue::NameOfStation(b))
+        | (OptionAndSubValue::AliasName(a), OptionAndSubValue::AliasName(b)) => {
+            a.as_ref().eq_ignore_ascii_case(b.as_ref())
+        }
+        // the device has at least the requested roles
+        (OptionAndSubValue::DeviceRole(a), OptionAndSubValue::DeviceRole(b)) => b.contains(*a),
+        (a, b) => a == b,
+    }
+}
@@ -1,18 +1,24 @@
-use crate::block::{BlockCommonWithoutInfo, BlockPadding, BlockTrait};
+use crate::block::{block_len, BlockCommonWithoutInfo, BlockPadding, BlockTrait};
 use crate::comm::BytesWrap;
-use crate::options::OptionAndSubValue;
-use crate::packet::{DcpHead, PnDcp, PnDcpTy};
-use anyhow::{bail, Result};
+use crate::error::{DcpError, Result};
+use crate::options::{NameOfStation, OptionAndSub, OptionAndSubValue};
+use crate::packet::block_iter::{DcpBlockIter, DcpBlockRef, DcpBlocks};
+use crate::packet::ident_filter::IdentFilter;
+use crate::packet::response_delay::ResponseDelayFactor;
+use crate::packet::{DcpEncode, DcpHead, DcpParse, PnDcp, PnDcpTy};
+use crate::parse::ParseContext;
+use bytes::BufMut;
 use pn_dcp_macro::derefmut;
 use pnet::util::MacAddr;
 use std::ops::{Deref, DerefMut};
+use std::time::Duration;
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum IdentReqBlock {
     Block(BlockCommonWithoutInfo),
     Padding(BlockPadding),
 }
-#[derive(Debug, Eq, PartialEq, Clone)]
+#[derive(Debug, Eq, PartialEq, Default, Clone)]
 #[derefmut(0)]
 pub struct IdentReqBlocks(Vec<IdentReqBlock>);
 
@@ -35,7 +41,7 @@ impl BlockTrait for IdentReqBlocks {
         unreachable!()
     }
 
-    fn append_data(&self, data: &mut Vec<u8>) {
+    fn append_data(&self, data: &mut impl BufMut) {
         for block in &self.0 {
             block.append_data(data)
         }
@@ -57,7 +63,7 @@ impl BlockTrait for IdentReqBlock {
         }
     }
 
-    fn append_data(&self, data: &mut Vec<u8>) {
+    fn append_data(&self, data: &mut impl BufMut) {
         match self {
             Self::Padding(a) => a.append_data(data),
             Self::Block(a) => a.append_data(data),
@@ -65,30 +71,29 @@ impl BlockTrait for IdentReqBlock {
     }
 }
 
-impl Default for IdentReqBlocks {
-    fn default() -> Self {
-        Self(Vec::default())
-    }
-}
-
 impl TryFrom<BytesWrap> for IdentReqBlocks {
-    type Error = anyhow::Error;
+    type Error = DcpError;
 
     fn try_from(value: BytesWrap) -> Result<Self, Self::Error> {
+        Self::parse(value, &mut ParseContext::default())
+    }
+}
+
+impl IdentReqBlocks {
+    pub(crate) fn parse(value: BytesWrap, ctx: &mut ParseContext) -> Result<Self> {
         let mut index = 0usize;
         let mut blocks = Vec::<IdentReqBlock>::new();
         while let Ok(tmp) = value.slice(index..) {
-            if tmp.len() == 0 {
+            if tmp.is_empty() {
                 break;
             }
-            let one = BlockCommonWithoutInfo::try_from(tmp)?;
-            let len = one.len();
-            if one.len() % 2 == 1 {
-                blocks.push(one.into());
+            let option = OptionAndSub::try_from(tmp.clone())?;
+            let len = block_len(option, &tmp)?;
+            blocks.push(BlockCommonWithoutInfo::parse(tmp, ctx)?.into());
+            if len % 2 == 1 {
+                ctx.check_padding(&value, index + len)?;
                 blocks.push(BlockPadding.into());
                 index += 1;
-            } else {
-                blocks.push(one.into());
             }
             index += len;
         }
@@ -110,14 +115,49 @@ impl Deref for PacketIdentReq {
     }
 }
 impl PacketIdentReq {
+    // sent to the PROFINET multicast address
     pub fn new(source: MacAddr) -> Self {
         let destination = MacAddr::new(0x01, 0x0e, 0xcf, 0x00, 0x00, 0x00);
+        Self::new_unicast(source, destination)
+    }
+    pub fn new_unicast(source: MacAddr, destination: MacAddr) -> Self {
         let head = DcpHead::new(destination, source, PnDcpTy::IdentReq);
         Self {
             head,
             blocks: IdentReqBlocks::default(),
         }
     }
+    pub fn new_by_filter(source: MacAddr, filter: &IdentFilter) -> Self {
+        let mut req = Self::new(source);
+        req.append_filter(filter);
+        req
+    }
+    pub fn new_identify_all(source: MacAddr) -> Self {
+        Self::new_by_filter(source, &IdentFilter::identify_all())
+    }
+    pub fn new_by_name_of_station(source: MacAddr, name: &NameOfStation) -> Self {
+        Self::new_by_filter(source, &IdentFilter::by_name_of_station(name))
+    }
+    pub fn new_by_alias(source: MacAddr, alias: &str) -> Self {
+        Self::new_by_filter(source, &IdentFilter::by_alias(alias))
+    }
+    pub fn with_response_delay(mut self, window: Duration) -> Self {
+        self.set_response_delay(window);
+        self
+    }
+    pub fn response_delay_factor(&self) -> ResponseDelayFactor {
+        ResponseDelayFactor::from(self.head.reserved_or_delay)
+    }
+    pub fn set_response_delay_factor(&mut self, factor: ResponseDelayFactor) {
+        self.head.set_reserved_or_delay(factor.to_u8_array());
+    }
+    pub fn set_response_delay(&mut self, window: Duration) {
+        self.set_response_delay_factor(ResponseDelayFactor::from_duration(window));
+    }
+    // how long the device with `mac` waits before it answers this request
+    pub fn response_delay_for(&self, mac: MacAddr) -> Duration {
+        self.response_delay_factor().delay_for(mac, self.head.xid)
+    }
     fn append_block(&mut self, block: impl Into<IdentReqBlock>) {
         let block = block.into();
         let block_len = block.len();
@@ -131,6 +171,18 @@ impl PacketIdentReq {
     pub fn append_block_by_option(&mut self, option: OptionAndSubValue) {
         self.append_block(BlockCommonWithoutInfo(option));
     }
+    pub fn append_filter(&mut self, filter: &IdentFilter) {
+        for option in filter.options() {
+            self.append_block_by_option(option.clone());
+        }
+    }
+    pub fn filter(&self) -> IdentFilter {
+        IdentFilter::from(self)
+    }
+    // whether a device with the blocks of `device` has to answer this request
+    pub fn matches(&self, device: &impl DcpBlocks) -> bool {
+        self.filter().matches(device)
+    }
 
     pub fn options(&self) -> Vec<OptionAndSubValue> {
         let mut options = Vec::new();
@@ -143,31 +195,67 @@ impl PacketIdentReq {
     }
 
     pub fn to_vec(&self) -> Vec<u8> {
-        let mut data = Vec::with_capacity(self.head.payload_len + 26);
-        self.head.append_data(&mut data);
-        self.blocks.append_data(&mut data);
+        let mut data = Vec::with_capacity(self.encoded_len());
+        self.write_to(&mut data);
         data
     }
 }
 
 impl TryFrom<PnDcp> for PacketIdentReq {
-    type Error = anyhow::Error;
+    type Error = DcpError;
 
     fn try_from(dcg: PnDcp) -> Result<Self, Self::Error> {
-        let PnDcp { head, blocks } = dcg;
+        Self::parse_pn_dcp(dcg, &mut ParseContext::default())
+    }
+}
+
+impl DcpParse for PacketIdentReq {
+    fn parse_pn_dcp(dcg: PnDcp, ctx: &mut ParseContext) -> Result<Self> {
+        let PnDcp { mut head, blocks } = dcg;
         if head.ty != PnDcpTy::IdentReq {
-            bail!("the packet is pn-dcp, but not ident req!");
+            return Err(DcpError::UnexpectedServiceType {
+                expected: PnDcpTy::IdentReq,
+                got: head.ty,
+            });
         }
-        let blocks = IdentReqBlocks::try_from(blocks)?;
+        let blocks = IdentReqBlocks::parse(blocks, ctx)?;
+        head.set_payload_len(blocks.len());
         Ok(Self { blocks, head })
     }
 }
 
 impl TryFrom<&[u8]> for PacketIdentReq {
-    type Error = anyhow::Error;
+    type Error = DcpError;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
         let dcg = PnDcp::try_from(value)?;
         PacketIdentReq::try_from(dcg)
     }
 }
+
+impl IdentReqBlock {
+    pub fn block_ref(&self) -> Option<DcpBlockRef<'_>> {
+        match self {
+            Self::Block(a) => Some(DcpBlockRef::Request(a)),
+            Self::Padding(_) => None,
+        }
+    }
+}
+
+impl DcpBlocks for PacketIdentReq {
+    fn iter_blocks(&self) -> DcpBlockIter<'_> {
+        DcpBlockIter::new(self.blocks.0.iter().filter_map(IdentReqBlock::block_ref))
+    }
+}
+
+impl DcpEncode for PacketIdentReq {
+    fn encoded_len(&self) -> usize {
+        self.head.frame_len()
+    }
+
+    fn write_to(&self, buf: &mut impl BufMut) {
+        self.head.append_data(buf);
+        self.blocks.append_data(buf);
+        self.head.append_padding(buf);
+    }
+}
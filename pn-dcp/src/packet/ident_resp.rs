@@ -1,10 +1,16 @@
-use crate::block::{BlockCommon, BlockIp, BlockPadding, BlockTrait};
+use crate::block::{block_len, BlockCommon, BlockFullIpSuite, BlockIp, BlockPadding, BlockTrait};
 use crate::comm::BytesWrap;
+use crate::error::{DcpError, Result};
 use crate::options::IpBlockInfo;
-use crate::options::{BlockInfo, InnerIpAddr, OptionAndSub, OptionAndSubValue};
+use crate::options::{
+    BlockInfo, DeviceInitiative, InnerFullIpSuite, InnerIpAddr, NameOfStation, OptionAndSub,
+    OptionAndSubValue,
+};
+use crate::packet::block_iter::{DcpBlockIter, DcpBlockRef, DcpBlocks};
 use crate::packet::ident_req::PacketIdentReq;
-use crate::packet::{DcpHead, PnDcp, PnDcpTy};
-use anyhow::{bail, Result};
+use crate::packet::{DcpEncode, DcpHead, DcpParse, PnDcp, PnDcpTy};
+use crate::parse::{ParseContext, ParseWarning};
+use bytes::BufMut;
 use pn_dcp_macro::derefmut;
 use pnet::util::MacAddr;
 use std::ops::{Deref, DerefMut};
@@ -13,6 +19,7 @@ use std::ops::{Deref, DerefMut};
 pub enum IdentRespBlock {
     Block(BlockCommon),
     BlockIp(BlockIp),
+    BlockFullIpSuite(BlockFullIpSuite),
     Padding(BlockPadding),
 }
 
@@ -44,7 +51,7 @@ impl BlockTrait for IdentRespBlocks {
         unreachable!()
     }
 
-    fn append_data(&self, data: &mut Vec<u8>) {
+    fn append_data(&self, data: &mut impl BufMut) {
         for block in &self.0 {
             block.append_data(data)
         }
@@ -56,6 +63,7 @@ impl BlockTrait for IdentRespBlock {
         match self {
             Self::Block(a) => a.len(),
             Self::BlockIp(a) => a.len(),
+            Self::BlockFullIpSuite(a) => a.len(),
             Self::Padding(a) => a.len(),
         }
     }
@@ -64,48 +72,52 @@ impl BlockTrait for IdentRespBlock {
         match self {
             Self::Block(a) => a.payload(),
             Self::BlockIp(a) => a.payload(),
+            Self::BlockFullIpSuite(a) => a.payload(),
             Self::Padding(a) => a.payload(),
         }
     }
 
-    fn append_data(&self, data: &mut Vec<u8>) {
+    fn append_data(&self, data: &mut impl BufMut) {
         match self {
             Self::Padding(a) => a.append_data(data),
             Self::BlockIp(a) => a.append_data(data),
+            Self::BlockFullIpSuite(a) => a.append_data(data),
             Self::Block(a) => a.append_data(data),
         }
     }
 }
 
 impl TryFrom<BytesWrap> for IdentRespBlocks {
-    type Error = anyhow::Error;
+    type Error = DcpError;
 
     fn try_from(value: BytesWrap) -> Result<Self, Self::Error> {
+        Self::parse(value, &mut ParseContext::default())
+    }
+}
+
+impl IdentRespBlocks {
+    pub(crate) fn parse(value: BytesWrap, ctx: &mut ParseContext) -> Result<Self> {
         let mut index = 0usize;
         let mut blocks = Vec::<IdentRespBlock>::new();
         while let Ok(tmp) = value.slice(index..) {
-            if tmp.len() == 0 {
+            if tmp.is_empty() {
                 break;
             }
             let option = OptionAndSub::try_from(tmp.clone())?;
-            let len = match option {
-                OptionAndSub::IpAddr => {
-                    let block = BlockIp::try_from_bytes(tmp)?;
-                    let len = block.len();
-                    blocks.push(block.into());
-                    len
-                }
+            let len = block_len(option, &tmp)?;
+            match option {
+                OptionAndSub::IpAddr => blocks.push(BlockIp::parse(tmp, ctx)?.into()),
+                OptionAndSub::FullIpSuite => blocks.push(BlockFullIpSuite::parse(tmp, ctx)?.into()),
                 OptionAndSub::Response => {
-                    bail!("todo OptionAndSub::Response")
+                    // skipped together with its padding
+                    ctx.report(ParseWarning::UnexpectedBlock(option))?;
+                    index += len + len % 2;
+                    continue;
                 }
-                option => {
-                    let block = BlockCommon::try_from_bytes(option, tmp)?;
-                    let len = block.len();
-                    blocks.push(block.into());
-                    len
-                }
-            };
+                option => blocks.push(BlockCommon::parse(option, tmp, ctx)?.into()),
+            }
             if len % 2 == 1 {
+                ctx.check_padding(&value, index + len)?;
                 blocks.push(BlockPadding.into());
                 index += 1;
             }
@@ -138,8 +150,9 @@ impl PacketIdentResp {
         }
     }
     pub fn from_req(source: MacAddr, ident_req: PacketIdentReq) -> Self {
-        let mut head = DcpHead::new(ident_req.source.clone(), source, PnDcpTy::IdentRespSuc);
+        let mut head = DcpHead::new(ident_req.source, source, PnDcpTy::IdentRespSuc);
         head.set_xid(ident_req.xid);
+        head.set_vlan(ident_req.vlan);
         Self {
             head,
             blocks: IdentRespBlocks::default(),
@@ -158,6 +171,9 @@ impl PacketIdentResp {
     pub fn append_block_ip(&mut self, ip: InnerIpAddr, info: IpBlockInfo) {
         self.append_block(BlockIp { ip, info })
     }
+    pub fn append_block_full_ip_suite(&mut self, ip: InnerFullIpSuite, info: IpBlockInfo) {
+        self.append_block(BlockFullIpSuite { ip, info })
+    }
     pub fn append_block_common(&mut self, option: OptionAndSubValue, info: BlockInfo) {
         self.append_block(BlockCommon { option, info })
     }
@@ -168,9 +184,8 @@ impl PacketIdentResp {
         })
     }
     pub fn to_vec(&self) -> Vec<u8> {
-        let mut data = Vec::with_capacity(self.head.payload_len + 26);
-        self.head.append_data(&mut data);
-        self.blocks.append_data(&mut data);
+        let mut data = Vec::with_capacity(self.encoded_len());
+        self.write_to(&mut data);
         data
     }
 
@@ -180,7 +195,43 @@ impl PacketIdentResp {
                 return Ok(ip.clone());
             }
         }
-        bail!("not contain ip info!");
+        Err(DcpError::MissingBlock(OptionAndSub::IpAddr))
+    }
+    pub fn block_full_ip_suite(&self) -> Result<BlockFullIpSuite> {
+        for block in self.blocks.iter() {
+            if let IdentRespBlock::BlockFullIpSuite(ip) = block {
+                return Ok(ip.clone());
+            }
+        }
+        Err(DcpError::MissingBlock(OptionAndSub::FullIpSuite))
+    }
+    // the name as the device reports it, devices do not always follow the naming rules
+    pub fn raw_name_of_station(&self) -> Result<BytesWrap> {
+        for block in self.blocks.iter() {
+            if let IdentRespBlock::Block(BlockCommon {
+                option: OptionAndSubValue::NameOfStation(a),
+                ..
+            }) = block
+            {
+                return Ok(a.clone());
+            }
+        }
+        Err(DcpError::MissingBlock(OptionAndSub::NameOfStation))
+    }
+    pub fn name_of_station(&self) -> Result<NameOfStation> {
+        NameOfStation::try_from(self.raw_name_of_station()?)
+    }
+    pub fn device_initiative(&self) -> Option<DeviceInitiative> {
+        for block in self.blocks.iter() {
+            if let IdentRespBlock::Block(BlockCommon {
+                option: OptionAndSubValue::DeviceInitiative(a),
+                ..
+            }) = block
+            {
+                return Some(*a);
+            }
+        }
+        None
     }
     pub fn block_commons(&self) -> Vec<BlockCommon> {
         let mut blocks = Vec::new();
@@ -194,23 +245,57 @@ impl PacketIdentResp {
 }
 
 impl TryFrom<PnDcp> for PacketIdentResp {
-    type Error = anyhow::Error;
+    type Error = DcpError;
 
     fn try_from(dcg: PnDcp) -> Result<Self, Self::Error> {
-        let PnDcp { head, blocks } = dcg;
-        if head.ty != PnDcpTy::IdentRespSuc {
-            bail!("the packet is pn-dcp, but not ident resp success!");
-        }
-        let blocks = IdentRespBlocks::try_from(blocks)?;
+        Self::parse_pn_dcp(dcg, &mut ParseContext::default())
+    }
+}
+
+impl DcpParse for PacketIdentResp {
+    fn parse_pn_dcp(dcg: PnDcp, ctx: &mut ParseContext) -> Result<Self> {
+        let PnDcp { mut head, blocks } = dcg;
+        head.ty.expect_response(PnDcpTy::IdentRespSuc)?;
+        let blocks = IdentRespBlocks::parse(blocks, ctx)?;
+        head.set_payload_len(blocks.len());
         Ok(Self { blocks, head })
     }
 }
 
 impl TryFrom<&[u8]> for PacketIdentResp {
-    type Error = anyhow::Error;
+    type Error = DcpError;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
         let dcg = PnDcp::try_from(value)?;
         PacketIdentResp::try_from(dcg)
     }
 }
+
+impl IdentRespBlock {
+    pub fn block_ref(&self) -> Option<DcpBlockRef<'_>> {
+        match self {
+            Self::Block(a) => Some(DcpBlockRef::Common(a)),
+            Self::BlockIp(a) => Some(DcpBlockRef::Ip(a)),
+            Self::BlockFullIpSuite(a) => Some(DcpBlockRef::FullIpSuite(a)),
+            Self::Padding(_) => None,
+        }
+    }
+}
+
+impl DcpBlocks for PacketIdentResp {
+    fn iter_blocks(&self) -> DcpBlockIter<'_> {
+        DcpBlockIter::new(self.blocks.0.iter().filter_map(IdentRespBlock::block_ref))
+    }
+}
+
+impl DcpEncode for PacketIdentResp {
+    fn encoded_len(&self) -> usize {
+        self.head.frame_len()
+    }
+
+    fn write_to(&self, buf: &mut impl BufMut) {
+        self.head.append_data(buf);
+        self.blocks.append_data(buf);
+        self.head.append_padding(buf);
+    }
+}
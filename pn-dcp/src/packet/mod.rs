@@ -1,14 +1,21 @@
+pub mod block_iter;
+pub mod dcp_packet;
 pub mod extend_trait;
 pub mod get_req;
 pub mod get_resp;
+pub mod hello_req;
+pub mod hello_resp;
+pub mod ident_filter;
 pub mod ident_req;
 pub mod ident_resp;
+pub mod response_delay;
 pub mod set_req;
 pub mod set_resp;
-
 use crate::comm::BytesWrap;
-use crate::comm::PROFINET_ETHER_TYPE;
-use anyhow::{bail, Result};
+use crate::comm::{ETHERNET_MIN_FRAME_LEN, PROFINET_ETHER_TYPE, VLAN_ETHER_TYPE};
+use crate::error::{DcpError, Result};
+use crate::parse::{ParseContext, ParseOptions, ParseWarning, Parsed};
+use bytes::BufMut;
 use pnet::util::MacAddr;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -18,13 +25,13 @@ pub enum PnDcpTy {
     HelloRespUnsup, // 0xfe, 0xfc, 0x06, 0x05
     IdentReq,       // 0xfe, 0xfe, 0x05, 0x00
     IdentRespSuc,   // 0xfe, 0xff, 0x05, 0x01
-    // IdentRespUnsup, // 0xfe, 0xff, 0x05, 0x05
-    GetReq,       // 0xfe, 0xfd, 0x03, 0x00
-    GetRespSuc,   // 0xfe, 0xfd, 0x03, 0x01
-    GetRespUnsup, // 0xfe, 0xfd, 0x03, 0x05
-    SetReq,       // 0xfe, 0xfd, 0x04, 0x00
-    SetRespSuc,   // 0xfe, 0xfd, 0x04, 0x01
-    SetRespUnsup, // 0xfe, 0xfd, 0x04, 0x05
+    IdentRespUnsup, // 0xfe, 0xff, 0x05, 0x05
+    GetReq,         // 0xfe, 0xfd, 0x03, 0x00
+    GetRespSuc,     // 0xfe, 0xfd, 0x03, 0x01
+    GetRespUnsup,   // 0xfe, 0xfd, 0x03, 0x05
+    SetReq,         // 0xfe, 0xfd, 0x04, 0x00
+    SetRespSuc,     // 0xfe, 0xfd, 0x04, 0x01
+    SetRespUnsup,   // 0xfe, 0xfd, 0x04, 0x05
 }
 impl PnDcpTy {
     pub fn to_u8_array(&self) -> [u8; 4] {
@@ -34,7 +41,7 @@ impl PnDcpTy {
             Self::HelloRespUnsup => [0xfe, 0xfc, 0x06, 0x05],
             Self::IdentReq => [0xfe, 0xfe, 0x05, 0x00],
             Self::IdentRespSuc => [0xfe, 0xff, 0x05, 0x01],
-            // Self::IdentRespUnsup => [0xfe, 0xff, 0x05, 0x05],
+            Self::IdentRespUnsup => [0xfe, 0xff, 0x05, 0x05],
             Self::GetReq => [0xfe, 0xfd, 0x03, 0x00],
             Self::GetRespSuc => [0xfe, 0xfd, 0x03, 0x01],
             Self::GetRespUnsup => [0xfe, 0xfd, 0x03, 0x05],
@@ -43,9 +50,56 @@ impl PnDcpTy {
             Self::SetRespUnsup => [0xfe, 0xfd, 0x04, 0x05],
         }
     }
+    // None for requests
+    pub fn status(&self) -> Option<RespStatus> {
+        match self {
+            Self::HelloRespSuc | Self::IdentRespSuc | Self::GetRespSuc | Self::SetRespSuc => {
+                Some(RespStatus::Success)
+            }
+            Self::HelloRespUnsup
+            | Self::IdentRespUnsup
+            | Self::GetRespUnsup
+            | Self::SetRespUnsup => Some(RespStatus::Unsupported),
+            Self::HelloReq | Self::IdentReq | Self::GetReq | Self::SetReq => None,
+        }
+    }
+    // the same response with `status`, requests are returned as they are
+    pub fn with_status(self, status: RespStatus) -> Self {
+        let success = status == RespStatus::Success;
+        match self {
+            Self::HelloRespSuc | Self::HelloRespUnsup if success => Self::HelloRespSuc,
+            Self::HelloRespSuc | Self::HelloRespUnsup => Self::HelloRespUnsup,
+            Self::IdentRespSuc | Self::IdentRespUnsup if success => Self::IdentRespSuc,
+            Self::IdentRespSuc | Self::IdentRespUnsup => Self::IdentRespUnsup,
+            Self::GetRespSuc | Self::GetRespUnsup if success => Self::GetRespSuc,
+            Self::GetRespSuc | Self::GetRespUnsup => Self::GetRespUnsup,
+            Self::SetRespSuc | Self::SetRespUnsup if success => Self::SetRespSuc,
+            Self::SetRespSuc | Self::SetRespUnsup => Self::SetRespUnsup,
+            a => a,
+        }
+    }
+    // the unsupported response has the same blocks, so either status of `expected` is accepted
+    pub fn expect_response(self, expected: PnDcpTy) -> Result<()> {
+        if self.with_status(RespStatus::Success) != expected.with_status(RespStatus::Success) {
+            return Err(DcpError::UnexpectedServiceType {
+                expected,
+                got: self,
+            });
+        }
+        Ok(())
+    }
+}
+
+// Status carried in the service type of a response
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RespStatus {
+    Success,
+    // the service or the requested option is not supported
+    Unsupported,
 }
+
 impl TryFrom<[u8; 4]> for PnDcpTy {
-    type Error = anyhow::Error;
+    type Error = DcpError;
     fn try_from(value: [u8; 4]) -> std::result::Result<Self, Self::Error> {
         match value {
             [0xfe, 0xfc, 0x06, 0x00] => Ok(Self::HelloReq),
@@ -53,14 +107,14 @@ impl TryFrom<[u8; 4]> for PnDcpTy {
             [0xfe, 0xfc, 0x06, 0x05] => Ok(Self::HelloRespUnsup),
             [0xfe, 0xfe, 0x05, 0x00] => Ok(Self::IdentReq),
             [0xfe, 0xff, 0x05, 0x01] => Ok(Self::IdentRespSuc),
-            // [0xfe, 0xff, 0x05, 0x05] => Ok(Self::IdentRespUnsup),
+            [0xfe, 0xff, 0x05, 0x05] => Ok(Self::IdentRespUnsup),
             [0xfe, 0xfd, 0x03, 0x00] => Ok(Self::GetReq),
             [0xfe, 0xfd, 0x03, 0x01] => Ok(Self::GetRespSuc),
             [0xfe, 0xfd, 0x03, 0x05] => Ok(Self::GetRespUnsup),
             [0xfe, 0xfd, 0x04, 0x00] => Ok(Self::SetReq),
             [0xfe, 0xfd, 0x04, 0x01] => Ok(Self::SetRespSuc),
             [0xfe, 0xfd, 0x04, 0x05] => Ok(Self::SetRespUnsup),
-            _ => bail!("not a pn-dcp type!"),
+            a => Err(DcpError::UnknownServiceType(a)),
         }
     }
 }
@@ -69,94 +123,265 @@ pub struct PnDcp {
     pub blocks: BytesWrap,
 }
 
-impl TryFrom<&[u8]> for PnDcp {
-    type Error = anyhow::Error;
-
-    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+impl PnDcp {
+    pub fn parse(value: &[u8], ctx: &mut ParseContext) -> Result<Self> {
         let head = DcpHead::try_from(value)?;
-        if let Some(blocks_data) = value.get(26..(26 + head.payload_len)) {
+        let start = head.header_len();
+        let end = start + head.payload_len;
+        if let Some(blocks_data) = value.get(start..end) {
+            // ethernet padding behind the payload is zero
+            if let Some(offset) = value[end..].iter().position(|x| *x != 0) {
+                ctx.report(ParseWarning::TrailingData {
+                    offset: end + offset,
+                    len: value.len() - end - offset,
+                })?;
+            }
             let blocks: BytesWrap = blocks_data.to_vec().into();
             return Ok(Self { head, blocks });
         }
-        bail!("长度不足，非PnDcg包");
+        Err(DcpError::Truncated {
+            needed: end,
+            got: value.len(),
+        })
+    }
+}
+
+impl TryFrom<&[u8]> for PnDcp {
+    type Error = DcpError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Self::parse(value, &mut ParseContext::default())
+    }
+}
+
+// Parsing with options, `TryFrom` parses leniently and drops the warnings
+pub trait DcpParse: Sized {
+    fn parse_pn_dcp(dcg: PnDcp, ctx: &mut ParseContext) -> Result<Self>;
+    // the only way to get the warnings, every `TryFrom` impl parses with a default (lenient)
+    // context and throws them away
+    fn parse_with(value: &[u8], options: ParseOptions) -> Result<Parsed<Self>> {
+        let mut ctx = ParseContext::new(options);
+        let dcg = PnDcp::parse(value, &mut ctx)?;
+        let packet = Self::parse_pn_dcp(dcg, &mut ctx)?;
+        Ok(Parsed {
+            packet,
+            warnings: ctx.into_warnings(),
+        })
+    }
+}
+
+// Serialization into any BufMut or a caller provided buffer, without allocating
+pub trait DcpEncode {
+    fn encoded_len(&self) -> usize;
+    // panics if `buf` has less than `encoded_len()` bytes remaining
+    fn write_to(&self, buf: &mut impl BufMut);
+    fn write_to_slice(&self, buf: &mut [u8]) -> Result<usize> {
+        let len = self.encoded_len();
+        if buf.len() < len {
+            return Err(DcpError::BufferTooSmall {
+                needed: len,
+                got: buf.len(),
+            });
+        }
+        let mut slice = &mut buf[..len];
+        self.write_to(&mut slice);
+        Ok(len)
+    }
+}
+
+// 802.1Q tag, PROFINET frames use priority 6 and VID 0 by default
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct VlanTag {
+    pub pcp: u8,
+    pub dei: bool,
+    pub vid: u16,
+}
+
+impl VlanTag {
+    pub fn new(pcp: u8, vid: u16) -> Self {
+        Self {
+            pcp: pcp & 0x07,
+            dei: false,
+            vid: vid & 0x0fff,
+        }
+    }
+    pub fn from_tci(tci: u16) -> Self {
+        Self {
+            pcp: (tci >> 13) as u8,
+            dei: tci & 0x1000 != 0,
+            vid: tci & 0x0fff,
+        }
+    }
+    pub fn tci(&self) -> u16 {
+        ((self.pcp as u16 & 0x07) << 13) | ((self.dei as u16) << 12) | (self.vid & 0x0fff)
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Clone)]
+impl Default for VlanTag {
+    fn default() -> Self {
+        Self::new(6, 0)
+    }
+}
+
+#[derive(Debug, Eq, Clone)]
 pub struct DcpHead {
     pub destination: MacAddr,
     pub source: MacAddr,
+    pub vlan: Option<VlanTag>,
     pub ty: PnDcpTy,
     pub xid: [u8; 4],
     pub reserved_or_delay: [u8; 2],
     pub payload_len: usize,
+    // pad the serialized frame to the ethernet minimum
+    pub min_frame_padding: bool,
+}
+
+// the padding is an encode option and not part of the frame, so it is left out
+impl PartialEq for DcpHead {
+    fn eq(&self, other: &Self) -> bool {
+        self.destination == other.destination
+            && self.source == other.source
+            && self.vlan == other.vlan
+            && self.ty == other.ty
+            && self.xid == other.xid
+            && self.reserved_or_delay == other.reserved_or_delay
+            && self.payload_len == other.payload_len
+    }
 }
 
 impl DcpHead {
-    pub fn append_data(&self, data: &mut Vec<u8>) {
-        data.extend_from_slice(self.destination.octets().as_slice());
-        data.extend_from_slice(self.source.octets().as_slice());
-        data.extend_from_slice(PROFINET_ETHER_TYPE.0.to_be_bytes().as_slice());
-        data.extend_from_slice(self.ty.to_u8_array().as_slice());
-        data.extend_from_slice(self.xid.as_slice());
-        data.extend_from_slice(self.reserved_or_delay.as_slice());
-        data.extend_from_slice((self.payload_len as u16).to_be_bytes().as_slice());
+    pub fn append_data(&self, data: &mut impl BufMut) {
+        data.put_slice(self.destination.octets().as_slice());
+        data.put_slice(self.source.octets().as_slice());
+        if let Some(vlan) = self.vlan {
+            data.put_u16(VLAN_ETHER_TYPE.0);
+            data.put_u16(vlan.tci());
+        }
+        data.put_slice(PROFINET_ETHER_TYPE.0.to_be_bytes().as_slice());
+        data.put_slice(self.ty.to_u8_array().as_slice());
+        data.put_slice(self.xid.as_slice());
+        data.put_slice(self.reserved_or_delay.as_slice());
+        data.put_slice((self.payload_len as u16).to_be_bytes().as_slice());
     }
     pub fn new(destination: MacAddr, source: MacAddr, ty: PnDcpTy) -> Self {
         Self {
             destination,
             source,
+            vlan: None,
             ty,
             xid: [0u8; 4],
             reserved_or_delay: [0u8; 2],
             payload_len: 0,
+            min_frame_padding: false,
         }
     }
+    pub fn set_vlan(&mut self, vlan: Option<VlanTag>) {
+        self.vlan = vlan;
+    }
+    pub fn set_min_frame_padding(&mut self, padding: bool) {
+        self.min_frame_padding = padding;
+    }
+    // ethernet header, vlan tag and dcp header
+    pub fn header_len(&self) -> usize {
+        if self.vlan.is_some() {
+            30
+        } else {
+            26
+        }
+    }
+    // a tagged frame is padded so it still has the minimum size once the tag is removed
+    pub fn padding_len(&self) -> usize {
+        if !self.min_frame_padding {
+            return 0;
+        }
+        let min = ETHERNET_MIN_FRAME_LEN + self.header_len() - 26;
+        min.saturating_sub(self.header_len() + self.payload_len)
+    }
+    // the whole serialized frame
+    pub fn frame_len(&self) -> usize {
+        self.header_len() + self.payload_len + self.padding_len()
+    }
+    pub fn append_padding(&self, data: &mut impl BufMut) {
+        data.put_bytes(0, self.padding_len());
+    }
     pub fn set_xid(&mut self, xid: [u8; 4]) {
         self.xid = xid;
     }
     pub fn set_reserved_or_delay(&mut self, reserved_or_delay: [u8; 2]) {
         self.reserved_or_delay = reserved_or_delay;
     }
-    // pub fn set_payload_len(&mut self, payload_len: usize) {
-    //     self.payload_len = payload_len;
-    // }
+    // after a lenient parse the kept blocks can be shorter than the received payload
+    pub(crate) fn set_payload_len(&mut self, payload_len: usize) {
+        self.payload_len = payload_len;
+    }
     pub fn add_payload_len(&mut self, add: usize) {
         self.payload_len += add;
     }
+    pub fn status(&self) -> Option<RespStatus> {
+        self.ty.status()
+    }
+    pub fn set_status(&mut self, status: RespStatus) {
+        self.ty = self.ty.with_status(status);
+    }
+}
+
+impl DcpEncode for DcpHead {
+    fn encoded_len(&self) -> usize {
+        self.header_len()
+    }
+
+    fn write_to(&self, buf: &mut impl BufMut) {
+        self.append_data(buf);
+    }
 }
 
 impl TryFrom<&[u8]> for DcpHead {
-    type Error = anyhow::Error;
+    type Error = DcpError;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        let vlan = match value.get(12..16) {
+            Some(a) if u16::from_be_bytes([a[0], a[1]]) == VLAN_ETHER_TYPE.0 => {
+                Some(VlanTag::from_tci(u16::from_be_bytes([a[2], a[3]])))
+            }
+            _ => None,
+        };
+        // the fields behind the ethernet addresses move by the length of the tag
+        let o = if vlan.is_some() { 4 } else { 0 };
         if let Some(payload_len) = value
-            .get(25)
-            .and_then(|x| Some(u16::from_be_bytes([value[24], *x]) as usize))
+            .get(25 + o)
+            .map(|x| u16::from_be_bytes([value[24 + o], *x]) as usize)
         {
-            if PROFINET_ETHER_TYPE.0 != u16::from_be_bytes([value[12], value[13]]) {
-                bail!("the packet is not a pn-dcp!");
-
+            if PROFINET_ETHER_TYPE.0 != u16::from_be_bytes([value[12 + o], value[13 + o]]) {
+                return Err(DcpError::NotProfinet);
             }
-            let ty = PnDcpTy::try_from([value[14], value[15], value[16], value[17]])?;
-            if payload_len + 26 > value.len() {
-                bail!("payload({}) + 26 > {}", payload_len, value.len());
+            let ty =
+                PnDcpTy::try_from([value[14 + o], value[15 + o], value[16 + o], value[17 + o]])?;
+            if payload_len + 26 + o > value.len() {
+                return Err(DcpError::Truncated {
+                    needed: payload_len + 26 + o,
+                    got: value.len(),
+                });
             }
             let destination =
                 MacAddr::new(value[0], value[1], value[2], value[3], value[4], value[5]);
             let source = MacAddr::new(value[6], value[7], value[8], value[9], value[10], value[11]);
-            let xid: [u8; 4] = [value[18], value[19], value[20], value[21]];
-            let reserved_or_delay: [u8; 2] = [value[22], value[23]];
+            let xid: [u8; 4] = [value[18 + o], value[19 + o], value[20 + o], value[21 + o]];
+            let reserved_or_delay: [u8; 2] = [value[22 + o], value[23 + o]];
             return Ok(DcpHead {
                 destination,
                 source,
+                vlan,
                 ty,
                 xid,
                 reserved_or_delay,
                 payload_len,
+                min_frame_padding: false,
             });
         }
-        bail!("长度不足，非PnDcg包");
+        Err(DcpError::Truncated {
+            needed: 26 + o,
+            got: value.len(),
+        })
     }
 }
 
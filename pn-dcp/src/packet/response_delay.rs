@@ -0,0 +1,64 @@
+use pnet::util::MacAddr;
+use std::time::Duration;
+
+pub const RESPONSE_DELAY_UNIT: Duration = Duration::from_millis(10);
+
+// ResponseDelayFactor of an ident request, devices spread their answers over factor × 10 ms.
+// 0x0000 is reserved and handled like 0x0001, which means answer at once
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct ResponseDelayFactor(u16);
+
+impl ResponseDelayFactor {
+    pub const NO_DELAY: Self = Self(0x0001);
+    pub const MAX: Self = Self(0x1900);
+
+    pub fn new(factor: u16) -> Self {
+        Self(factor)
+    }
+    // the smallest factor with a window of at least `window`, limited to MAX
+    pub fn from_duration(window: Duration) -> Self {
+        let unit = RESPONSE_DELAY_UNIT.as_nanos();
+        let factor = window.as_nanos().div_ceil(unit);
+        Self(factor.clamp(1, Self::MAX.0 as u128) as u16)
+    }
+    pub fn value(&self) -> u16 {
+        self.0
+    }
+    pub fn to_u8_array(&self) -> [u8; 2] {
+        self.0.to_be_bytes()
+    }
+    pub fn window(&self) -> Duration {
+        RESPONSE_DELAY_UNIT * self.0 as u32
+    }
+    // a multiple of 10 ms below the window, derived from the device mac and the request xid so
+    // devices answering the same request spread out, while a device repeats its delay for a
+    // repeated request
+    pub fn delay_for(&self, mac: MacAddr, xid: [u8; 4]) -> Duration {
+        if self.0 <= 1 {
+            return Duration::ZERO;
+        }
+        let mut seed = [0u8; 8];
+        seed[..6].copy_from_slice(&mac.octets());
+        let seed = u64::from_be_bytes(seed) ^ u32::from_be_bytes(xid) as u64;
+        RESPONSE_DELAY_UNIT * (splitmix64(seed) % self.0 as u64) as u32
+    }
+}
+
+impl From<[u8; 2]> for ResponseDelayFactor {
+    fn from(a: [u8; 2]) -> Self {
+        Self(u16::from_be_bytes(a))
+    }
+}
+
+impl Default for ResponseDelayFactor {
+    fn default() -> Self {
+        Self::NO_DELAY
+    }
+}
+
+fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
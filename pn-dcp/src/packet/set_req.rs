@@ -1,17 +1,23 @@
-use crate::block::{BlockPadding, BlockSet, BlockTrait};
+use crate::block::{block_len, BlockPadding, BlockSet, BlockTrait};
 use crate::comm::BytesWrap;
-use crate::options::{BlockQualifier, OptionAndSubValue};
-use crate::packet::{DcpHead, PnDcp, PnDcpTy};
-use anyhow::bail;
+use crate::error::{DcpError, Result};
+use crate::options::{
+    BlockQualifier, InnerFullIpSuite, InnerIpAddr, NameOfStation, OptionAndSub, OptionAndSubValue,
+};
+use crate::packet::block_iter::{DcpBlockIter, DcpBlockRef, DcpBlocks};
+use crate::packet::{DcpEncode, DcpHead, DcpParse, PnDcp, PnDcpTy};
+use crate::parse::ParseContext;
+use bytes::BufMut;
 use pn_dcp_macro::derefmut;
 use pnet::datalink::MacAddr;
+use std::net::Ipv4Addr;
 use std::ops::{Deref, DerefMut};
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 #[derefmut(head)]
 pub struct PacketSetReq {
     head: DcpHead,
-    blocks: BlockSet,
+    blocks: SetReqBlocks,
 }
 impl Deref for PacketSetReq {
     type Target = DcpHead;
@@ -27,39 +33,113 @@ impl PacketSetReq {
         option: OptionAndSubValue,
         qualifier: BlockQualifier,
     ) -> Self {
-        let mut head = DcpHead::new(dest, source, PnDcpTy::SetReq);
-        let blocks = BlockSet { option, qualifier };
-        head.add_payload_len(blocks.len());
-        Self { head, blocks }
+        let mut req = Self::new_empty(source, dest);
+        req.append_block(option, qualifier);
+        req
+    }
+    pub fn new_name_of_station(
+        source: MacAddr,
+        dest: MacAddr,
+        name: &NameOfStation,
+        qualifier: BlockQualifier,
+    ) -> Self {
+        Self::new(source, dest, name.to_option(), qualifier)
+    }
+    // validate the ip suite before building, use new with ip.to_option() to skip it
+    pub fn try_new_ip(
+        source: MacAddr,
+        dest: MacAddr,
+        ip: InnerIpAddr,
+        qualifier: BlockQualifier,
+    ) -> Result<Self> {
+        ip.validate()?;
+        Ok(Self::new(source, dest, ip.to_option(), qualifier))
+    }
+    // 0.0.0.0/0.0.0.0/0.0.0.0 resets the ip of the device
+    pub fn new_reset_ip(source: MacAddr, dest: MacAddr, qualifier: BlockQualifier) -> Self {
+        let ip = InnerIpAddr::new_by_ipv4(
+            Ipv4Addr::UNSPECIFIED,
+            Ipv4Addr::UNSPECIFIED,
+            Ipv4Addr::UNSPECIFIED,
+        );
+        Self::new(source, dest, ip.to_option(), qualifier)
+    }
+    pub fn try_new_full_ip_suite(
+        source: MacAddr,
+        dest: MacAddr,
+        ip: InnerFullIpSuite,
+        qualifier: BlockQualifier,
+    ) -> Result<Self> {
+        ip.validate()?;
+        Ok(Self::new(source, dest, ip.to_option(), qualifier))
+    }
+    pub fn new_empty(source: MacAddr, dest: MacAddr) -> Self {
+        let head = DcpHead::new(dest, source, PnDcpTy::SetReq);
+        Self {
+            head,
+            blocks: SetReqBlocks::default(),
+        }
+    }
+    pub fn append_block(&mut self, option: OptionAndSubValue, qualifier: BlockQualifier) {
+        self.append_block_set(BlockSet { option, qualifier });
+    }
+    pub fn append_control(&mut self, option: OptionAndSubValue) -> Result<()> {
+        self.append_block_set(BlockSet::new_control(option)?);
+        Ok(())
+    }
+    pub fn append_block_set(&mut self, block: BlockSet) {
+        let block = SetReqBlock::Set(block);
+        let block_len = block.len();
+        self.blocks.0.push(block);
+        self.head.add_payload_len(block_len);
+        if block_len % 2 == 1 {
+            self.blocks.0.push(SetReqBlock::Padding(BlockPadding));
+            self.head.add_payload_len(1);
+        }
     }
 
-    pub fn blocks(&self) -> BlockSet {
-        self.blocks.clone()
+    pub fn blocks(&self) -> Vec<BlockSet> {
+        let mut blocks = Vec::new();
+        for block in self.blocks.iter() {
+            if let SetReqBlock::Set(set) = block {
+                blocks.push(set.clone());
+            }
+        }
+        blocks
     }
 
     pub fn to_vec(&self) -> Vec<u8> {
-        let mut data = Vec::with_capacity(self.head.payload_len + 26);
-        self.head.append_data(&mut data);
-        self.blocks.append_data(&mut data);
+        let mut data = Vec::with_capacity(self.encoded_len());
+        self.write_to(&mut data);
         data
     }
 }
 
 impl TryFrom<PnDcp> for PacketSetReq {
-    type Error = anyhow::Error;
+    type Error = DcpError;
 
     fn try_from(dcg: PnDcp) -> Result<Self, Self::Error> {
-        let PnDcp { head, blocks } = dcg;
+        Self::parse_pn_dcp(dcg, &mut ParseContext::default())
+    }
+}
+
+impl DcpParse for PacketSetReq {
+    fn parse_pn_dcp(dcg: PnDcp, ctx: &mut ParseContext) -> Result<Self> {
+        let PnDcp { mut head, blocks } = dcg;
         if head.ty != PnDcpTy::SetReq {
-            bail!("the packet is pn-dcp, but not  set req!");
+            return Err(DcpError::UnexpectedServiceType {
+                expected: PnDcpTy::SetReq,
+                got: head.ty,
+            });
         }
-        let blocks = BlockSet::try_from(blocks)?;
+        let blocks = SetReqBlocks::parse(blocks, ctx)?;
+        head.set_payload_len(blocks.len());
         Ok(Self { blocks, head })
     }
 }
 
 impl TryFrom<&[u8]> for PacketSetReq {
-    type Error = anyhow::Error;
+    type Error = DcpError;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
         let dcg = PnDcp::try_from(value)?;
@@ -67,7 +147,7 @@ impl TryFrom<&[u8]> for PacketSetReq {
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq, Clone)]
 pub enum SetReqBlock {
     Set(BlockSet),
     Padding(BlockPadding),
@@ -87,7 +167,7 @@ impl BlockTrait for SetReqBlock {
         }
     }
 
-    fn append_data(&self, data: &mut Vec<u8>) {
+    fn append_data(&self, data: &mut impl BufMut) {
         match self {
             Self::Padding(a) => a.append_data(data),
             Self::Set(a) => a.append_data(data),
@@ -95,23 +175,50 @@ impl BlockTrait for SetReqBlock {
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Default)]
+#[derive(Debug, Eq, PartialEq, Default, Clone)]
 #[derefmut(0)]
 pub struct SetReqBlocks(pub(crate) Vec<SetReqBlock>);
 
+impl BlockTrait for SetReqBlocks {
+    fn len(&self) -> usize {
+        let mut len = 0;
+        for block in &self.0 {
+            len += block.len();
+        }
+        len
+    }
+
+    fn payload(&self) -> u16 {
+        unreachable!()
+    }
+
+    fn append_data(&self, data: &mut impl BufMut) {
+        for block in &self.0 {
+            block.append_data(data)
+        }
+    }
+}
+
 impl TryFrom<BytesWrap> for SetReqBlocks {
-    type Error = anyhow::Error;
+    type Error = DcpError;
     fn try_from(value: BytesWrap) -> Result<Self, Self::Error> {
+        Self::parse(value, &mut ParseContext::default())
+    }
+}
+
+impl SetReqBlocks {
+    pub(crate) fn parse(value: BytesWrap, ctx: &mut ParseContext) -> Result<Self> {
         let mut index = 0usize;
         let mut blocks = Vec::<SetReqBlock>::new();
         while let Ok(tmp) = value.slice(index..) {
-            if tmp.len() == 0 {
+            if tmp.is_empty() {
                 break;
             }
-            let block = BlockSet::try_from(tmp)?;
-            let len = block.len();
-            blocks.push(block.into());
+            let option = OptionAndSub::try_from(tmp.clone())?;
+            let len = block_len(option, &tmp)?;
+            blocks.push(BlockSet::parse(tmp, ctx)?.into());
             if len % 2 == 1 {
+                ctx.check_padding(&value, index + len)?;
                 blocks.push(BlockPadding.into());
                 index += 1;
             }
@@ -120,3 +227,30 @@ impl TryFrom<BytesWrap> for SetReqBlocks {
         Ok(blocks.into())
     }
 }
+
+impl SetReqBlock {
+    pub fn block_ref(&self) -> Option<DcpBlockRef<'_>> {
+        match self {
+            Self::Set(a) => Some(DcpBlockRef::Set(a)),
+            Self::Padding(_) => None,
+        }
+    }
+}
+
+impl DcpBlocks for PacketSetReq {
+    fn iter_blocks(&self) -> DcpBlockIter<'_> {
+        DcpBlockIter::new(self.blocks.0.iter().filter_map(SetReqBlock::block_ref))
+    }
+}
+
+impl DcpEncode for PacketSetReq {
+    fn encoded_len(&self) -> usize {
+        self.head.frame_len()
+    }
+
+    fn write_to(&self, buf: &mut impl BufMut) {
+        self.head.append_data(buf);
+        self.blocks.append_data(buf);
+        self.head.append_padding(buf);
+    }
+}
@@ -1,8 +1,12 @@
-use crate::block::{BlockPadding, BlockResp, BlockTrait};
+use crate::block::{block_len, BlockPadding, BlockResp, BlockSet, BlockTrait};
 use crate::comm::BytesWrap;
+use crate::error::{DcpError, Result};
 use crate::options::{BlockError, OptionAndSub};
-use crate::packet::{DcpHead, PnDcp, PnDcpTy};
-use anyhow::bail;
+use crate::packet::block_iter::{DcpBlockIter, DcpBlockRef, DcpBlocks};
+use crate::packet::set_req::PacketSetReq;
+use crate::packet::{DcpEncode, DcpHead, DcpParse, PnDcp, PnDcpTy};
+use crate::parse::{ParseContext, ParseWarning};
+use bytes::BufMut;
 use pn_dcp_macro::derefmut;
 use pnet::datalink::MacAddr;
 use std::ops::{Deref, DerefMut};
@@ -25,18 +29,31 @@ impl Deref for PacketSetResp {
 pub struct SetRespBlocks(pub(crate) Vec<SetRespBlock>);
 
 impl TryFrom<BytesWrap> for SetRespBlocks {
-    type Error = anyhow::Error;
+    type Error = DcpError;
     fn try_from(value: BytesWrap) -> Result<Self, Self::Error> {
+        Self::parse(value, &mut ParseContext::default())
+    }
+}
+
+impl SetRespBlocks {
+    pub(crate) fn parse(value: BytesWrap, ctx: &mut ParseContext) -> Result<Self> {
         let mut index = 0usize;
         let mut blocks = Vec::<SetRespBlock>::new();
         while let Ok(tmp) = value.slice(index..) {
-            if tmp.len() == 0 {
+            if tmp.is_empty() {
                 break;
             }
-            let option = BlockResp::try_from(tmp.clone())?;
-            let len = option.len();
-            blocks.push(option.into());
+            let option = OptionAndSub::try_from(tmp.clone())?;
+            let len = block_len(option, &tmp)?;
+            if option != OptionAndSub::Response {
+                // skipped together with its padding
+                ctx.report(ParseWarning::UnexpectedBlock(option))?;
+                index += len + len % 2;
+                continue;
+            }
+            blocks.push(BlockResp::parse(tmp, ctx)?.into());
             if len % 2 == 1 {
+                ctx.check_padding(&value, index + len)?;
                 blocks.push(BlockPadding.into());
                 index += 1;
             }
@@ -59,7 +76,7 @@ impl BlockTrait for SetRespBlocks {
         unreachable!()
     }
 
-    fn append_data(&self, data: &mut Vec<u8>) {
+    fn append_data(&self, data: &mut impl BufMut) {
         for block in &self.0 {
             block.append_data(data)
         }
@@ -86,7 +103,7 @@ impl BlockTrait for SetRespBlock {
         }
     }
 
-    fn append_data(&self, data: &mut Vec<u8>) {
+    fn append_data(&self, data: &mut impl BufMut) {
         match self {
             Self::Padding(a) => a.append_data(data),
             Self::Response(a) => a.append_data(data),
@@ -105,6 +122,26 @@ impl PacketSetResp {
         resp.append_block(blocks);
         resp
     }
+    pub fn from_req(
+        source: MacAddr,
+        set_req: &PacketSetReq,
+        mut error: impl FnMut(&BlockSet) -> BlockError,
+    ) -> Self {
+        let mut head = DcpHead::new(set_req.source, source, PnDcpTy::SetRespSuc);
+        head.set_xid(set_req.xid);
+        head.set_vlan(set_req.vlan);
+        let mut resp = Self {
+            head,
+            blocks: SetRespBlocks::default(),
+        };
+        for block in set_req.blocks() {
+            resp.append_block_resp(block.option().option(), error(&block));
+        }
+        resp
+    }
+    pub fn append_block_resp(&mut self, option: OptionAndSub, error: BlockError) {
+        self.append_block(BlockResp(option, error))
+    }
     fn append_block(&mut self, block: impl Into<SetRespBlock>) {
         let block = block.into();
         let block_len = block.len();
@@ -125,33 +162,88 @@ impl PacketSetResp {
         }
         blocks
     }
+    pub fn failures(&self) -> Vec<BlockResp> {
+        let mut blocks = self.blocks();
+        blocks.retain(|x| x.1 != BlockError::Ok);
+        blocks
+    }
+    pub fn match_req(&self, set_req: &PacketSetReq) -> Result<Vec<(BlockSet, Option<BlockError>)>> {
+        if self.xid != set_req.xid {
+            return Err(DcpError::XidMismatch {
+                expected: set_req.xid,
+                got: self.xid,
+            });
+        }
+        let mut resps = self.blocks();
+        let mut matched = Vec::new();
+        for block in set_req.blocks() {
+            let option = block.option().option();
+            let error = resps
+                .iter()
+                .position(|x| x.0 == option)
+                .map(|index| resps.remove(index).1);
+            matched.push((block, error));
+        }
+        Ok(matched)
+    }
 
     pub fn to_vec(&self) -> Vec<u8> {
-        let mut data = Vec::with_capacity(self.head.payload_len + 26);
-        self.head.append_data(&mut data);
-        self.blocks.append_data(&mut data);
+        let mut data = Vec::with_capacity(self.encoded_len());
+        self.write_to(&mut data);
         data
     }
 }
 
 impl TryFrom<PnDcp> for PacketSetResp {
-    type Error = anyhow::Error;
+    type Error = DcpError;
 
     fn try_from(dcg: PnDcp) -> Result<Self, Self::Error> {
-        let PnDcp { head, blocks } = dcg;
-        if head.ty != PnDcpTy::SetRespSuc {
-            bail!("the packet is pn-dcp, but not set resp success!");
-        }
-        let blocks = SetRespBlocks::try_from(blocks)?;
+        Self::parse_pn_dcp(dcg, &mut ParseContext::default())
+    }
+}
+
+impl DcpParse for PacketSetResp {
+    fn parse_pn_dcp(dcg: PnDcp, ctx: &mut ParseContext) -> Result<Self> {
+        let PnDcp { mut head, blocks } = dcg;
+        head.ty.expect_response(PnDcpTy::SetRespSuc)?;
+        let blocks = SetRespBlocks::parse(blocks, ctx)?;
+        head.set_payload_len(blocks.len());
         Ok(Self { blocks, head })
     }
 }
 
 impl TryFrom<&[u8]> for PacketSetResp {
-    type Error = anyhow::Error;
+    type Error = DcpError;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
         let dcg = PnDcp::try_from(value)?;
         PacketSetResp::try_from(dcg)
     }
 }
+
+impl SetRespBlock {
+    pub fn block_ref(&self) -> Option<DcpBlockRef<'_>> {
+        match self {
+            Self::Response(a) => Some(DcpBlockRef::Resp(a)),
+            Self::Padding(_) => None,
+        }
+    }
+}
+
+impl DcpBlocks for PacketSetResp {
+    fn iter_blocks(&self) -> DcpBlockIter<'_> {
+        DcpBlockIter::new(self.blocks.0.iter().filter_map(SetRespBlock::block_ref))
+    }
+}
+
+impl DcpEncode for PacketSetResp {
+    fn encoded_len(&self) -> usize {
+        self.head.frame_len()
+    }
+
+    fn write_to(&self, buf: &mut impl BufMut) {
+        self.head.append_data(buf);
+        self.blocks.append_data(buf);
+        self.head.append_padding(buf);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::comm::BytesWrap;
+use crate::error::{DcpError, Result};
+use crate::options::OptionAndSub;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub struct ParseOptions {
+    // reject the frame on the first warning instead of collecting it
+    pub strict: bool,
+}
+
+impl ParseOptions {
+    pub fn strict() -> Self {
+        Self { strict: true }
+    }
+    pub fn lenient() -> Self {
+        Self { strict: false }
+    }
+}
+
+// Deviation from the spec that the parser can tolerate
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ParseWarning {
+    // the block carries more bytes than the option needs
+    LengthMismatch {
+        option: OptionAndSub,
+        expected: usize,
+        got: usize,
+    },
+    // offset in the dcp payload
+    NonZeroPadding {
+        offset: usize,
+        value: u8,
+    },
+    UnknownBlockInfo {
+        option: OptionAndSub,
+        info: [u8; 2],
+    },
+    // the block is not allowed in this packet and was skipped
+    UnexpectedBlock(OptionAndSub),
+    // non-zero bytes behind the dcp payload, offset in the frame
+    TrailingData {
+        offset: usize,
+        len: usize,
+    },
+}
+
+impl Display for ParseWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LengthMismatch {
+                option,
+                expected,
+                got,
+            } => write!(
+                f,
+                "value of {:?} has {} bytes, expect {}",
+                option, got, expected
+            ),
+            Self::NonZeroPadding { offset, value } => {
+                write!(f, "padding at {} is {:#04x}, not zero", offset, value)
+            }
+            Self::UnknownBlockInfo { option, info } => {
+                write!(f, "unknown block info of {:?}: {:02x?}", option, info)
+            }
+            Self::UnexpectedBlock(option) => write!(f, "unexpected block {:?}", option),
+            Self::TrailingData { offset, len } => {
+                write!(f, "{} bytes of trailing data at {}", len, offset)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Parsed<T> {
+    pub packet: T,
+    pub warnings: Vec<ParseWarning>,
+}
+
+// Options and collected warnings of one parse
+#[derive(Debug, Clone, Default)]
+pub struct ParseContext {
+    options: ParseOptions,
+    warnings: Vec<ParseWarning>,
+}
+
+impl ParseContext {
+    pub fn new(options: ParseOptions) -> Self {
+        Self {
+            options,
+            warnings: Vec::new(),
+        }
+    }
+    pub fn options(&self) -> ParseOptions {
+        self.options
+    }
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+    pub fn into_warnings(self) -> Vec<ParseWarning> {
+        self.warnings
+    }
+    // an error in strict mode, otherwise the warning is collected
+    pub fn report(&mut self, warning: ParseWarning) -> Result<()> {
+        if self.options.strict {
+            return Err(DcpError::Strict(warning));
+        }
+        self.warnings.push(warning);
+        Ok(())
+    }
+    pub(crate) fn check_len(
+        &mut self,
+        option: OptionAndSub,
+        expected: usize,
+        got: usize,
+    ) -> Result<()> {
+        if expected != got {
+            self.report(ParseWarning::LengthMismatch {
+                option,
+                expected,
+                got,
+            })?;
+        }
+        Ok(())
+    }
+    // the padding byte behind an odd block, a missing one at the end is tolerated
+    pub(crate) fn check_padding(&mut self, data: &BytesWrap, offset: usize) -> Result<()> {
+        match data.as_ref().get(offset) {
+            Some(&value) if value != 0 => {
+                self.report(ParseWarning::NonZeroPadding { offset, value })
+            }
+            _ => Ok(()),
+        }
+    }
+}
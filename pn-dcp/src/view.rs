@@ -0,0 +1,177 @@
+use crate::comm::BytesWrap;
+use crate::error::{DcpError, Result};
+use crate::options::{OptionAndSub, OptionAndSubValue, ResetFactoryMode};
+use crate::packet::{DcpHead, PnDcp, PnDcpTy};
+
+// Borrowed view of a pn-dcp frame, the lengths of the head and all blocks are checked once in
+// `new`, so iterating the blocks does not fail and does not copy
+#[derive(Debug, Clone)]
+pub struct DcpFrameView<'a> {
+    head: DcpHead,
+    payload: &'a [u8],
+}
+
+impl<'a> DcpFrameView<'a> {
+    pub fn new(data: &'a [u8]) -> Result<Self> {
+        let head = DcpHead::try_from(data)?;
+        let start = head.header_len();
+        let payload = &data[start..(start + head.payload_len)];
+        let view = Self { head, payload };
+        let mut index = 0usize;
+        while index < payload.len() {
+            // an odd get req payload ends with a zero padding byte
+            if view.options_only() && payload[index..] == [0x00] {
+                break;
+            }
+            let len = view.check_block(&payload[index..])?;
+            index += len + len % 2;
+        }
+        Ok(view)
+    }
+    // returns the block length without padding
+    fn check_block(&self, data: &[u8]) -> Result<usize> {
+        if data.len() < 2 {
+            return Err(DcpError::Truncated {
+                needed: 2,
+                got: data.len(),
+            });
+        }
+        if self.options_only() {
+            return Ok(2);
+        }
+        let option = OptionAndSub::new(data[0], data[1])?;
+        let Some(len) = data.get(2..4) else {
+            return Err(DcpError::Truncated {
+                needed: 4,
+                got: data.len(),
+            });
+        };
+        let declared = u16::from_be_bytes([len[0], len[1]]) as usize;
+        let available = data.len() - 4;
+        let min = if has_info(self.head.ty, option) { 2 } else { 0 };
+        if declared < min || declared > available {
+            return Err(DcpError::BadBlockLength {
+                option,
+                declared,
+                available,
+            });
+        }
+        Ok(declared + 4)
+    }
+    // the blocks of get req are only option and suboption
+    fn options_only(&self) -> bool {
+        self.head.ty == PnDcpTy::GetReq
+    }
+    pub fn head(&self) -> &DcpHead {
+        &self.head
+    }
+    pub fn ty(&self) -> PnDcpTy {
+        self.head.ty
+    }
+    pub fn payload(&self) -> &'a [u8] {
+        self.payload
+    }
+    pub fn blocks(&self) -> BlockIter<'a> {
+        BlockIter {
+            data: self.payload,
+            ty: self.head.ty,
+        }
+    }
+    pub fn to_pn_dcp(&self) -> PnDcp {
+        PnDcp {
+            head: self.head.clone(),
+            blocks: BytesWrap::from(self.payload),
+        }
+    }
+    // convert to the owned packet type, e.g. `view.to_packet::<PacketIdentResp>()`
+    pub fn to_packet<T: TryFrom<PnDcp, Error = DcpError>>(&self) -> Result<T> {
+        T::try_from(self.to_pn_dcp())
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for DcpFrameView<'a> {
+    type Error = DcpError;
+
+    fn try_from(value: &'a [u8]) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+// Borrowed block: option, suboption, block length, BlockInfo/BlockQualifier and value
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct BlockView<'a> {
+    option: OptionAndSub,
+    data: &'a [u8],
+    has_info: bool,
+}
+
+impl<'a> BlockView<'a> {
+    pub fn option(&self) -> OptionAndSub {
+        self.option
+    }
+    // the whole block without padding
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+    pub fn block_len(&self) -> usize {
+        self.data
+            .get(2..4)
+            .map_or(0, |x| u16::from_be_bytes([x[0], x[1]]) as usize)
+    }
+    // BlockInfo in responses, BlockQualifier in set req
+    pub fn info(&self) -> Option<[u8; 2]> {
+        if self.has_info {
+            Some([self.data[4], self.data[5]])
+        } else {
+            None
+        }
+    }
+    pub fn value(&self) -> &'a [u8] {
+        match (self.data.len(), self.has_info) {
+            (2, _) => &[],
+            (_, true) => &self.data[6..],
+            (_, false) => &self.data[4..],
+        }
+    }
+    pub fn to_option_value(&self) -> Result<OptionAndSubValue> {
+        match (self.option, self.info()) {
+            (OptionAndSub::ResetFactory, Some(qualifier)) => Ok(OptionAndSubValue::ResetFactory(
+                ResetFactoryMode::from(qualifier),
+            )),
+            (option, _) => OptionAndSubValue::init_by_ty(option, BytesWrap::from(self.value())),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BlockIter<'a> {
+    data: &'a [u8],
+    ty: PnDcpTy,
+}
+
+impl<'a> Iterator for BlockIter<'a> {
+    type Item = BlockView<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let data = self.data;
+        let option = OptionAndSub::new(*data.first()?, *data.get(1)?).ok()?;
+        let (len, has_info) = if self.ty == PnDcpTy::GetReq {
+            (2, false)
+        } else {
+            let len = u16::from_be_bytes([*data.get(2)?, *data.get(3)?]) as usize + 4;
+            (len, has_info(self.ty, option))
+        };
+        let block = data.get(..len)?;
+        self.data = data.get((len + len % 2)..).unwrap_or_default();
+        Some(BlockView {
+            option,
+            data: block,
+            has_info,
+        })
+    }
+}
+
+// blocks of ident req and get req, and response blocks carry no BlockInfo/BlockQualifier
+fn has_info(ty: PnDcpTy, option: OptionAndSub) -> bool {
+    !matches!(ty, PnDcpTy::IdentReq | PnDcpTy::GetReq) && option != OptionAndSub::Response
+}
@@ -0,0 +1,119 @@
+mod comm;
+
+use anyhow::Result;
+use comm::*;
+use pn_dcp::block::BlockResp;
+use pn_dcp::options::{BlockError, DeviceId, DeviceRole, InnerIpAddr, NameOfStation, OptionAndSub};
+use pn_dcp::packet::block_iter::{DcpBlockRef, DcpBlocks};
+use pn_dcp::packet::dcp_packet::DcpPacket;
+use pn_dcp::packet::get_req::PacketGetReq;
+use pn_dcp::packet::get_resp::PacketGetResp;
+use pn_dcp::packet::hello_req::PacketHelloReq;
+use pn_dcp::packet::ident_resp::PacketIdentResp;
+use pn_dcp::packet::set_req::PacketSetReq;
+use std::net::Ipv4Addr;
+
+fn station_ip(packet: &impl DcpBlocks) -> Option<(NameOfStation, InnerIpAddr)> {
+    Some((packet.find()?, packet.find()?))
+}
+
+#[test]
+fn ident_resp_blocks_test() -> Result<()> {
+    let data = get_ident_resp();
+    let resp = PacketIdentResp::try_from(data.as_slice())?;
+    assert_eq!(
+        resp.iter_blocks().map(|x| x.option()).collect::<Vec<_>>(),
+        vec![
+            OptionAndSub::ManufacturerSpecific,
+            OptionAndSub::DeviceOptions,
+            OptionAndSub::NameOfStation,
+            OptionAndSub::DeviceId,
+            OptionAndSub::DeviceRole,
+            OptionAndSub::IpAddr,
+        ]
+    );
+    assert_eq!(
+        resp.find::<NameOfStation>(),
+        Some(NameOfStation::new("bb-abci.111")?)
+    );
+    assert_eq!(resp.find::<DeviceId>(), Some(DeviceId::new(0x002a, 0x0000)));
+    assert_eq!(resp.find::<DeviceRole>(), Some(DeviceRole::IO_CONTROLLER));
+    assert_eq!(resp.find::<BlockResp>(), None);
+    let ip = resp.block_ip()?;
+    assert_eq!(
+        resp.find_option(OptionAndSub::IpAddr),
+        Some(DcpBlockRef::Ip(&ip))
+    );
+    assert!(resp.find_option(OptionAndSub::AliasName).is_none());
+    assert_eq!(
+        station_ip(&resp),
+        Some((NameOfStation::new("bb-abci.111")?, ip.ip().clone()))
+    );
+    Ok(())
+}
+
+#[test]
+fn get_blocks_test() -> Result<()> {
+    let req = PacketGetReq::try_from(get_get_req().as_slice())?;
+    assert_eq!(
+        req.iter_blocks().collect::<Vec<_>>(),
+        vec![
+            DcpBlockRef::Option(OptionAndSub::IpAddr),
+            DcpBlockRef::Option(OptionAndSub::DHCP(61))
+        ]
+    );
+    assert!(req.find_option(OptionAndSub::DHCP(61)).is_some());
+
+    let resp = PacketGetResp::try_from(get_get_resp().as_slice())?;
+    assert_eq!(
+        resp.find::<InnerIpAddr>(),
+        Some(InnerIpAddr::new_by_ipv4(
+            Ipv4Addr::new(192, 168, 199, 245),
+            Ipv4Addr::new(255, 255, 255, 0),
+            Ipv4Addr::new(192, 168, 199, 254),
+        ))
+    );
+    assert_eq!(
+        resp.find::<BlockResp>(),
+        Some(BlockResp(
+            OptionAndSub::DHCP(61),
+            BlockError::SuboptionUnsuppOrNoDataSetAvail
+        ))
+    );
+    assert_eq!(station_ip(&resp), None);
+    Ok(())
+}
+
+#[test]
+fn hello_and_set_blocks_test() -> Result<()> {
+    let hello = PacketHelloReq::try_from(get_hello_req().as_slice())?;
+    assert_eq!(
+        station_ip(&hello).map(|x| x.0),
+        Some(NameOfStation::new("bb-abci.111")?)
+    );
+
+    let set = PacketSetReq::try_from(get_set_req_multi().as_slice())?;
+    assert_eq!(set.iter_blocks().count(), 4);
+    assert_eq!(
+        set.find::<NameOfStation>(),
+        Some(NameOfStation::new("bb-abci.111")?)
+    );
+    assert_eq!(
+        set.find_option(OptionAndSub::EndTransaction)
+            .and_then(|x| x.option_value())
+            .map(|x| x.option()),
+        Some(OptionAndSub::EndTransaction)
+    );
+    Ok(())
+}
+
+#[test]
+fn dcp_packet_blocks_test() -> Result<()> {
+    let names = [get_ident_resp(), get_hello_req(), get_get_resp()]
+        .iter()
+        .map(|x| DcpPacket::try_from(x.as_slice()).map(|x| x.find::<NameOfStation>()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let name = NameOfStation::new("bb-abci.111")?;
+    assert_eq!(names, vec![Some(name.clone()), Some(name), None]);
+    Ok(())
+}
@@ -40,6 +40,14 @@ pub fn get_get_resp() -> Vec<u8> {
         0x04, 0x00, 0x03, 0x03, 0x3d, 0x02, 0x00,
     ]
 }
+pub fn get_get_resp_full_ip_suite() -> Vec<u8> {
+    vec![
+        0xec, 0x63, 0xd7, 0x5d, 0x5d, 0x22, 0x00, 0x1c, 0x06, 0x11, 0x42, 0x02, 0x88, 0x92, 0xfe,
+        0xfd, 0x03, 0x01, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x22, 0x01, 0x03, 0x00, 0x1e,
+        0x00, 0x01, 0xc0, 0xa8, 0xc7, 0xf5, 0xff, 0xff, 0xff, 0x00, 0xc0, 0xa8, 0xc7, 0xfe, 0x08,
+        0x08, 0x08, 0x08, 0xc0, 0xa8, 0xc7, 0xfe, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ]
+}
 pub fn get_set_req() -> Vec<u8> {
     vec![
         0x00, 0x1c, 0x06, 0x11, 0x42, 0x02, 0xec, 0x63, 0xd7, 0x5d, 0x5d, 0x22, 0x88, 0x92, 0xfe,
@@ -47,6 +55,15 @@ pub fn get_set_req() -> Vec<u8> {
         0x00, 0x01, 0xc0, 0xa8, 0xc7, 0xf5, 0xff, 0xff, 0xff, 0x00, 0xc0, 0xa8, 0xc7, 0xfe,
     ]
 }
+pub fn get_set_req_multi() -> Vec<u8> {
+    vec![
+        0x00, 0x1c, 0x06, 0x11, 0x42, 0x02, 0xec, 0x63, 0xd7, 0x5d, 0x5d, 0x22, 0x88, 0x92, 0xfe,
+        0xfd, 0x04, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x30, 0x05, 0x01, 0x00, 0x02,
+        0x00, 0x00, 0x01, 0x02, 0x00, 0x0e, 0x00, 0x01, 0xc0, 0xa8, 0xc7, 0xf5, 0xff, 0xff, 0xff,
+        0x00, 0xc0, 0xa8, 0xc7, 0xfe, 0x02, 0x02, 0x00, 0x0d, 0x00, 0x01, 0x62, 0x62, 0x2d, 0x61,
+        0x62, 0x63, 0x69, 0x2e, 0x31, 0x31, 0x31, 0x00, 0x05, 0x02, 0x00, 0x02, 0x00, 0x01,
+    ]
+}
 pub fn get_set_resp() -> Vec<u8> {
     vec![
         0xec, 0x63, 0xd7, 0x5d, 0x5d, 0x22, 0x00, 0x1c, 0x06, 0x11, 0x42, 0x02, 0x88, 0x92, 0xfe,
@@ -54,6 +71,32 @@ pub fn get_set_resp() -> Vec<u8> {
         0x01, 0x02, 0x00, 0x00,
     ]
 }
+pub fn get_set_resp_multi() -> Vec<u8> {
+    vec![
+        0xec, 0x63, 0xd7, 0x5d, 0x5d, 0x22, 0x00, 0x1c, 0x06, 0x11, 0x42, 0x02, 0x88, 0x92, 0xfe,
+        0xfd, 0x04, 0x01, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x20, 0x05, 0x04, 0x00, 0x03,
+        0x05, 0x01, 0x00, 0x00, 0x05, 0x04, 0x00, 0x03, 0x01, 0x02, 0x00, 0x00, 0x05, 0x04, 0x00,
+        0x03, 0x02, 0x02, 0x05, 0x00, 0x05, 0x04, 0x00, 0x03, 0x05, 0x02, 0x00, 0x00,
+    ]
+}
+
+pub fn get_hello_req() -> Vec<u8> {
+    vec![
+        0x01, 0x0e, 0xcf, 0x00, 0x00, 0x01, 0x00, 0x1c, 0x06, 0x11, 0x42, 0x02, 0x88, 0x92, 0xfe,
+        0xfc, 0x06, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x3e, 0x02, 0x02, 0x00, 0x0d,
+        0x00, 0x00, 0x62, 0x62, 0x2d, 0x61, 0x62, 0x63, 0x69, 0x2e, 0x31, 0x31, 0x31, 0x00, 0x01,
+        0x02, 0x00, 0x0e, 0x00, 0x01, 0xc0, 0xa8, 0xc7, 0xf5, 0xff, 0xff, 0xff, 0x00, 0xc0, 0xa8,
+        0xc7, 0xfe, 0x02, 0x03, 0x00, 0x06, 0x00, 0x00, 0x00, 0x2a, 0x00, 0x00, 0x02, 0x04, 0x00,
+        0x04, 0x00, 0x00, 0x02, 0x00, 0x06, 0x01, 0x00, 0x04, 0x00, 0x00, 0x00, 0x01,
+    ]
+}
+pub fn get_hello_resp() -> Vec<u8> {
+    vec![
+        0x00, 0x1c, 0x06, 0x11, 0x42, 0x02, 0xec, 0x63, 0xd7, 0x5d, 0x5d, 0x22, 0x88, 0x92, 0xfe,
+        0xfc, 0x06, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x08, 0x05, 0x04, 0x00, 0x03,
+        0x06, 0x01, 0x00, 0x00,
+    ]
+}
 
 pub fn get_ident_req_ext() -> Vec<u8> {
     vec![
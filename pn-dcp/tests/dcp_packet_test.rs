@@ -0,0 +1,122 @@
+mod comm;
+
+use anyhow::Result;
+use comm::*;
+use pn_dcp::error::DcpError;
+use pn_dcp::packet::block_iter::DcpBlocks;
+use pn_dcp::packet::dcp_packet::DcpPacket;
+use pn_dcp::packet::get_resp::PacketGetResp;
+use pn_dcp::packet::ident_req::PacketIdentReq;
+use pn_dcp::packet::ident_resp::PacketIdentResp;
+use pn_dcp::packet::{PnDcpTy, RespStatus};
+use pn_dcp::view::DcpFrameView;
+
+#[test]
+fn dispatch_test() -> Result<()> {
+    let cases = [
+        (get_hello_req(), PnDcpTy::HelloReq, false),
+        (get_hello_resp(), PnDcpTy::HelloRespSuc, true),
+        (get_ident_req(), PnDcpTy::IdentReq, false),
+        (get_ident_resp(), PnDcpTy::IdentRespSuc, true),
+        (get_get_req(), PnDcpTy::GetReq, false),
+        (get_get_resp(), PnDcpTy::GetRespSuc, true),
+        (get_set_req(), PnDcpTy::SetReq, false),
+        (get_set_resp(), PnDcpTy::SetRespSuc, true),
+    ];
+    for (data, ty, is_response) in cases {
+        let packet = DcpPacket::try_from(data.as_slice())?;
+        assert_eq!(packet.ty(), ty);
+        assert_eq!(packet.is_response(), is_response);
+        assert_eq!(packet.xid(), get_xid(data.as_slice()).unwrap());
+        assert_eq!(
+            packet.source(),
+            init_mac_by_array(get_src_array(data.as_slice()).unwrap())
+        );
+        assert_eq!(
+            packet.destination(),
+            init_mac_by_array(get_destination_array(data.as_slice()).unwrap())
+        );
+        assert_eq!(packet.to_vec(), data);
+    }
+    let data = get_ident_resp();
+    let packet = DcpPacket::try_from(data.as_slice())?;
+    let ident_resp = PacketIdentResp::try_from(data.as_slice())?;
+    assert_eq!(packet, DcpPacket::IdentResp(ident_resp.clone()));
+    assert_eq!(packet, ident_resp.into());
+    assert_eq!(
+        DcpFrameView::new(data.as_slice())?.to_packet::<DcpPacket>()?,
+        packet
+    );
+    Ok(())
+}
+
+#[test]
+fn unsupported_test() -> Result<()> {
+    let mut data = get_get_resp();
+    data[17] = 0x05;
+    let packet = DcpPacket::try_from(data.as_slice())?;
+    assert!(matches!(packet, DcpPacket::GetResp(_)));
+    assert_eq!(packet.ty(), PnDcpTy::GetRespUnsup);
+    assert_eq!(packet.head().status(), Some(RespStatus::Unsupported));
+    assert!(packet.is_response());
+    assert_eq!(packet.to_vec(), data);
+
+    data[17] = 0x07;
+    assert_eq!(
+        DcpPacket::try_from(data.as_slice()),
+        Err(DcpError::UnknownServiceType([0xfe, 0xfd, 0x03, 0x07]))
+    );
+    Ok(())
+}
+
+#[test]
+fn resp_status_test() -> Result<()> {
+    let cases = [
+        (get_hello_resp(), PnDcpTy::HelloRespUnsup),
+        (get_ident_resp(), PnDcpTy::IdentRespUnsup),
+        (get_get_resp(), PnDcpTy::GetRespUnsup),
+        (get_set_resp(), PnDcpTy::SetRespUnsup),
+    ];
+    for (mut data, unsup) in cases {
+        let mut packet = DcpPacket::try_from(data.as_slice())?;
+        assert_eq!(packet.head().status(), Some(RespStatus::Success));
+        data[17] = 0x05;
+        let negative = DcpPacket::try_from(data.as_slice())?;
+        assert_eq!(negative.ty(), unsup);
+        assert_eq!(negative.to_vec(), data);
+        assert_eq!(negative.iter_blocks().count(), packet.iter_blocks().count());
+        match &mut packet {
+            DcpPacket::HelloResp(a) => a.set_status(RespStatus::Unsupported),
+            DcpPacket::IdentResp(a) => a.set_status(RespStatus::Unsupported),
+            DcpPacket::GetResp(a) => a.set_status(RespStatus::Unsupported),
+            DcpPacket::SetResp(a) => a.set_status(RespStatus::Unsupported),
+            _ => unreachable!(),
+        }
+        assert_eq!(packet, negative);
+    }
+
+    let data = get_ident_req();
+    let mut ident_req = PacketIdentReq::try_from(data.as_slice())?;
+    assert_eq!(ident_req.status(), None);
+    ident_req.set_status(RespStatus::Unsupported);
+    assert_eq!(ident_req.ty, PnDcpTy::IdentReq);
+    assert_eq!(
+        PacketGetResp::try_from(get_set_resp().as_slice()),
+        Err(DcpError::UnexpectedServiceType {
+            expected: PnDcpTy::GetRespSuc,
+            got: PnDcpTy::SetRespSuc
+        })
+    );
+    assert_eq!(
+        PnDcpTy::IdentRespUnsup.expect_response(PnDcpTy::IdentRespSuc),
+        Ok(())
+    );
+    assert_eq!(
+        PnDcpTy::IdentReq.expect_response(PnDcpTy::IdentRespSuc),
+        Err(DcpError::UnexpectedServiceType {
+            expected: PnDcpTy::IdentRespSuc,
+            got: PnDcpTy::IdentReq
+        })
+    );
+    Ok(())
+}
@@ -0,0 +1,79 @@
+mod comm;
+
+use anyhow::Result;
+use bytes::BytesMut;
+use comm::*;
+use pn_dcp::error::DcpError;
+use pn_dcp::packet::dcp_packet::DcpPacket;
+use pn_dcp::packet::ident_resp::PacketIdentResp;
+use pn_dcp::packet::set_req::PacketSetReq;
+use pn_dcp::packet::{DcpEncode, DcpHead};
+
+#[test]
+fn encode_bytes_mut_test() -> Result<()> {
+    let frames = [
+        get_hello_req(),
+        get_hello_resp(),
+        get_ident_req(),
+        get_ident_resp(),
+        get_get_req(),
+        get_get_resp(),
+        get_set_req(),
+        get_set_req_multi(),
+        get_set_resp(),
+        get_set_resp_multi(),
+    ];
+    let mut buf = BytesMut::new();
+    for frame in frames {
+        let packet = DcpPacket::try_from(frame.as_slice())?;
+        assert_eq!(packet.encoded_len(), frame.len());
+        buf.clear();
+        packet.write_to(&mut buf);
+        assert_eq!(buf.as_ref(), frame.as_slice());
+    }
+    Ok(())
+}
+
+#[test]
+fn encode_slice_test() -> Result<()> {
+    let mut buf = [0u8; 1500];
+    let frame = get_ident_resp();
+    let packet = PacketIdentResp::try_from(frame.as_slice())?;
+    let len = packet.write_to_slice(&mut buf)?;
+    assert_eq!(len, frame.len());
+    assert_eq!(&buf[..len], frame.as_slice());
+
+    // the buffer is reused for the next packet
+    let frame = get_set_req();
+    let packet = PacketSetReq::try_from(frame.as_slice())?;
+    let len = packet.write_to_slice(&mut buf)?;
+    assert_eq!(&buf[..len], frame.as_slice());
+    assert_eq!(packet.to_vec(), frame);
+    Ok(())
+}
+
+#[test]
+fn encode_head_test() -> Result<()> {
+    let frame = get_ident_req();
+    let head = DcpHead::try_from(frame.as_slice())?;
+    let mut buf = [0u8; 26];
+    assert_eq!(head.write_to_slice(&mut buf)?, 26);
+    assert_eq!(buf.as_slice(), &frame[..26]);
+    Ok(())
+}
+
+#[test]
+fn encode_buffer_too_small_test() -> Result<()> {
+    let frame = get_ident_resp();
+    let packet = PacketIdentResp::try_from(frame.as_slice())?;
+    let mut buf = vec![0u8; frame.len() - 1];
+    assert_eq!(
+        packet.write_to_slice(&mut buf),
+        Err(DcpError::BufferTooSmall {
+            needed: frame.len(),
+            got: frame.len() - 1,
+        })
+    );
+    assert!(buf.iter().all(|x| *x == 0));
+    Ok(())
+}
@@ -0,0 +1,155 @@
+mod comm;
+
+use comm::*;
+use pn_dcp::error::DcpError;
+use pn_dcp::options::{BlockQualifier, DeviceId, OptionAndSub, OptionAndSubValue, SignalValue};
+use pn_dcp::packet::set_req::PacketSetReq;
+use pn_dcp::packet::set_resp::PacketSetResp;
+use pn_dcp::packet::{DcpHead, PnDcpTy};
+use std::ops::Bound;
+
+#[test]
+fn truncated_test() {
+    let data = get_set_req();
+    assert_eq!(
+        DcpHead::try_from(&data[..20]),
+        Err(DcpError::Truncated {
+            needed: 26,
+            got: 20
+        })
+    );
+    assert_eq!(
+        PacketSetReq::try_from(&data[..data.len() - 1]),
+        Err(DcpError::Truncated {
+            needed: data.len(),
+            got: data.len() - 1
+        })
+    );
+}
+
+#[test]
+fn slice_range_test() {
+    let data = pn_dcp::comm::BytesWrap::from(get_set_req());
+    let len = data.len();
+    assert_eq!(
+        data.slice(len + 2..),
+        Err(DcpError::Truncated {
+            needed: len + 2,
+            got: len
+        })
+    );
+    assert_eq!(
+        data.slice(4..len + 1),
+        Err(DcpError::Truncated {
+            needed: len + 1,
+            got: len
+        })
+    );
+    let (begin, end) = (6, 4);
+    assert_eq!(
+        data.slice(begin..end),
+        Err(DcpError::InvalidRange { begin, end })
+    );
+    assert_eq!(data.slice(len..).map(|x| x.len()), Ok(0));
+
+    let short = data.slice(..8).unwrap();
+    let (begin, end) = (10, 5);
+    assert_eq!(
+        short.slice(begin..end),
+        Err(DcpError::InvalidRange { begin, end })
+    );
+    assert_eq!(
+        short.slice(2..=usize::MAX),
+        Err(DcpError::InvalidRange {
+            begin: 2,
+            end: usize::MAX
+        })
+    );
+    assert_eq!(
+        short.slice((Bound::Excluded(usize::MAX), Bound::Unbounded)),
+        Err(DcpError::InvalidRange {
+            begin: usize::MAX,
+            end: usize::MAX
+        })
+    );
+}
+
+#[test]
+fn not_profinet_test() {
+    let mut data = get_set_req();
+    data[12..14].copy_from_slice(&[0x08, 0x00]);
+    assert_eq!(
+        PacketSetReq::try_from(data.as_slice()),
+        Err(DcpError::NotProfinet)
+    );
+}
+
+#[test]
+fn service_type_test() {
+    let mut data = get_set_req();
+    data[17] = 0x07;
+    assert_eq!(
+        PacketSetReq::try_from(data.as_slice()),
+        Err(DcpError::UnknownServiceType([0xfe, 0xfd, 0x04, 0x07]))
+    );
+    let err = PacketSetResp::try_from(get_set_req().as_slice()).unwrap_err();
+    assert_eq!(
+        err,
+        DcpError::UnexpectedServiceType {
+            expected: PnDcpTy::SetRespSuc,
+            got: PnDcpTy::SetReq
+        }
+    );
+}
+
+#[test]
+fn bad_block_length_test() {
+    let mut data = get_set_req();
+    data[29] = 0x10;
+    assert_eq!(
+        PacketSetReq::try_from(data.as_slice()),
+        Err(DcpError::BadBlockLength {
+            option: OptionAndSub::IpAddr,
+            declared: 16,
+            available: 14
+        })
+    );
+}
+
+#[test]
+fn unknown_block_error_test() {
+    let mut data = get_set_resp();
+    data[32] = 0x09;
+    assert_eq!(
+        PacketSetResp::try_from(data.as_slice()),
+        Err(DcpError::UnknownBlockError(0x09))
+    );
+}
+
+#[test]
+fn builder_error_test() {
+    let src = init_mac_by_array(get_src_array(get_set_req().as_slice()).unwrap());
+    let mut req = PacketSetReq::new_empty(src, src);
+    assert_eq!(
+        req.append_control(OptionAndSubValue::NameOfStation("a".as_bytes().into())),
+        Err(DcpError::NotControlOption(OptionAndSub::NameOfStation))
+    );
+    assert_eq!(
+        "0x002A".parse::<DeviceId>(),
+        Err(DcpError::InvalidDeviceId("0x002A".to_string()))
+    );
+    let err = pn_dcp::block::BlockSet::try_new(
+        OptionAndSubValue::Signal(SignalValue::FlashOnce),
+        BlockQualifier::SavePermanent,
+    )
+    .unwrap_err();
+    assert!(matches!(err, DcpError::InvalidQualifier { .. }));
+    assert_eq!(
+        DcpError::Truncated {
+            needed: 26,
+            got: 20
+        }
+        .to_string(),
+        "data too short: need 26 bytes, got 20"
+    );
+}
@@ -2,9 +2,12 @@ mod comm;
 
 use anyhow::Result;
 use comm::*;
-use pn_dcp::block::{BlockIp, BlockResp};
+use pn_dcp::block::{BlockFullIpSuite, BlockIp, BlockResp};
 use pn_dcp::options::IpBlockInfo;
-use pn_dcp::options::{BlockError, InnerIpAddr, OptionAndSub};
+use pn_dcp::options::{
+    BlockError, BlockInfo, DhcpClientId, DhcpValue, InnerFullIpSuite, InnerIpAddr, OptionAndSub,
+    OptionAndSubValue,
+};
 use pn_dcp::packet::get_req::PacketGetReq;
 use pn_dcp::packet::get_resp::PacketGetResp;
 use std::net::Ipv4Addr;
@@ -67,6 +70,104 @@ fn test_get_resp() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_get_resp_full_ip_suite() -> Result<()> {
+    let data = get_get_resp_full_ip_suite();
+    let get = PacketGetResp::try_from(data.as_slice())?;
+    let src = get_src_array(data.as_slice()).unwrap();
+    let dest = get_destination_array(data.as_slice()).unwrap();
+
+    let mut get_resp = PacketGetResp::new(init_mac_by_array(src), init_mac_by_array(dest));
+    get_resp.set_xid(get_xid(data.as_slice()).unwrap());
+
+    let ip = InnerIpAddr::new_by_ipv4(
+        Ipv4Addr::from(0xc0a8c7f5),
+        Ipv4Addr::from(0xffffff00),
+        Ipv4Addr::from(0xc0a8c7fe),
+    );
+    let suite = InnerFullIpSuite::new_by_ipv4(
+        ip,
+        [
+            Ipv4Addr::new(8, 8, 8, 8),
+            Ipv4Addr::from(0xc0a8c7fe),
+            Ipv4Addr::UNSPECIFIED,
+            Ipv4Addr::UNSPECIFIED,
+        ],
+    );
+    get_resp.append_block_full_ip_suite(suite.clone(), IpBlockInfo::IpSet);
+
+    assert_eq!(
+        get_resp.block_full_ip_suite()?,
+        BlockFullIpSuite::new(suite, IpBlockInfo::IpSet)
+    );
+    assert!(get_resp.block_ip().is_err());
+    assert_eq!(data, get_resp.to_vec());
+    assert_eq!(get, get_resp);
+    Ok(())
+}
+
+#[test]
+fn test_get_resp_mac() -> Result<()> {
+    let data = get_get_resp();
+    let src = init_mac_by_array(get_src_array(data.as_slice()).unwrap());
+    let dest = init_mac_by_array(get_destination_array(data.as_slice()).unwrap());
+
+    let mut get_resp = PacketGetResp::new(src, dest);
+    get_resp.append_block_common(OptionAndSubValue::MacAddr(src), BlockInfo::Reserved);
+    let bytes = get_resp.to_vec();
+    assert_eq!(
+        &bytes[26..],
+        &[0x01, 0x01, 0x00, 0x08, 0x00, 0x00, 0x00, 0x1c, 0x06, 0x11, 0x42, 0x02]
+    );
+
+    let parsed = PacketGetResp::try_from(bytes.as_slice())?;
+    assert_eq!(
+        parsed.block_commons()[0].option(),
+        &OptionAndSubValue::MacAddr(src)
+    );
+    assert_eq!(parsed, get_resp);
+    Ok(())
+}
+
+#[test]
+fn test_get_resp_dhcp() -> Result<()> {
+    let data = get_get_resp();
+    let src = init_mac_by_array(get_src_array(data.as_slice()).unwrap());
+    let dest = init_mac_by_array(get_destination_array(data.as_slice()).unwrap());
+
+    let mut get_resp = PacketGetResp::new(src, dest);
+    get_resp.append_block_common(
+        DhcpValue::ClientId(DhcpClientId::MacAddr).to_option(),
+        BlockInfo::Reserved,
+    );
+    get_resp.append_block_common(
+        DhcpValue::HostName("plc-1".as_bytes().into()).to_option(),
+        BlockInfo::Reserved,
+    );
+    let bytes = get_resp.to_vec();
+    assert_eq!(
+        &bytes[26..34],
+        &[0x03, 0x3d, 0x00, 0x04, 0x00, 0x00, 0x01, 0x01]
+    );
+
+    let parsed = PacketGetResp::try_from(bytes.as_slice())?;
+    let options: Vec<_> = parsed
+        .block_commons()
+        .iter()
+        .map(|x| x.option().clone())
+        .collect();
+    assert_eq!(
+        options,
+        vec![
+            OptionAndSubValue::DHCP(DhcpValue::ClientId(DhcpClientId::MacAddr)),
+            OptionAndSubValue::DHCP(DhcpValue::HostName("plc-1".as_bytes().into())),
+        ]
+    );
+    assert_eq!(options[0].option(), OptionAndSub::DHCP(61));
+    assert_eq!(parsed, get_resp);
+    Ok(())
+}
+
 #[test]
 fn test_ext() -> Result<()> {
     let req = PacketGetReq::try_from(get_get_req().as_slice())?;
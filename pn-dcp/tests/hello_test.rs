@@ -0,0 +1,76 @@
+mod comm;
+
+use anyhow::Result;
+use comm::*;
+use pn_dcp::block::{BlockCommon, BlockIp, BlockResp};
+use pn_dcp::options::{
+    BlockError, DeviceId, DeviceInitiative, DeviceRole, InnerIpAddr, IpBlockInfo, OptionAndSub,
+    OptionAndSubValue,
+};
+use pn_dcp::packet::hello_req::PacketHelloReq;
+use pn_dcp::packet::hello_resp::PacketHelloResp;
+use std::net::Ipv4Addr;
+
+#[test]
+fn hello_req_test() -> Result<()> {
+    let data = get_hello_req();
+    let req = PacketHelloReq::try_from(data.as_slice())?;
+    let src = get_src_array(data.as_slice()).unwrap();
+
+    let mut hello = PacketHelloReq::new(init_mac_by_array(src));
+    hello.set_xid(get_xid(data.as_slice()).unwrap());
+    let name = OptionAndSubValue::NameOfStation("bb-abci.111".as_bytes().into());
+    hello.append_block_common_default(name.clone());
+    let ip = InnerIpAddr::new_by_ipv4(
+        Ipv4Addr::from(0xc0a8c7f5),
+        Ipv4Addr::from(0xffffff00),
+        Ipv4Addr::from(0xc0a8c7fe),
+    );
+    hello.append_block_ip(ip.clone(), IpBlockInfo::IpSet);
+    let device_id = DeviceId::new(0x002a, 0x0000).to_option();
+    hello.append_block_common_default(device_id.clone());
+    let device_role = DeviceRole::IO_CONTROLLER.to_option();
+    hello.append_block_common_default(device_role.clone());
+    let initiative = DeviceInitiative::new(true).to_option();
+    hello.append_block_common_default(initiative.clone());
+
+    assert_eq!(hello.block_ip()?, BlockIp::new(ip, IpBlockInfo::IpSet));
+    assert_eq!(
+        hello.block_commons(),
+        vec![
+            BlockCommon::new(name),
+            BlockCommon::new(device_id),
+            BlockCommon::new(device_role),
+            BlockCommon::new(initiative)
+        ]
+    );
+    assert_eq!(data, hello.to_vec());
+    assert_eq!(req, hello);
+    Ok(())
+}
+
+#[test]
+fn hello_resp_test() -> Result<()> {
+    let data = get_hello_resp();
+    let resp = PacketHelloResp::try_from(data.as_slice())?;
+    let req = PacketHelloReq::try_from(get_hello_req().as_slice())?;
+    let src = get_src_array(data.as_slice()).unwrap();
+
+    let mut hello = PacketHelloResp::from_req(init_mac_by_array(src), &req);
+    hello.append_block_resp(OptionAndSub::DeviceInitiative, BlockError::Ok);
+
+    assert_eq!(
+        hello.block_resps(),
+        vec![BlockResp(OptionAndSub::DeviceInitiative, BlockError::Ok)]
+    );
+    assert_eq!(data, hello.to_vec());
+    assert_eq!(resp, hello);
+    Ok(())
+}
+
+#[test]
+fn hello_ty_test() -> Result<()> {
+    assert!(PacketHelloResp::try_from(get_hello_req().as_slice()).is_err());
+    assert!(PacketHelloReq::try_from(get_ident_req().as_slice()).is_err());
+    Ok(())
+}
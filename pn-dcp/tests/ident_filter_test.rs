@@ -0,0 +1,116 @@
+mod comm;
+
+use anyhow::Result;
+use comm::*;
+use pn_dcp::options::{DeviceId, DeviceRole, InnerIpAddr, NameOfStation, OptionAndSubValue};
+use pn_dcp::packet::ident_filter::IdentFilter;
+use pn_dcp::packet::ident_req::PacketIdentReq;
+use pn_dcp::packet::ident_resp::PacketIdentResp;
+use pnet::util::MacAddr;
+use std::net::Ipv4Addr;
+
+fn device_ip() -> InnerIpAddr {
+    InnerIpAddr::new_by_ipv4(
+        Ipv4Addr::new(192, 168, 199, 245),
+        Ipv4Addr::new(255, 255, 255, 0),
+        Ipv4Addr::new(192, 168, 199, 254),
+    )
+}
+
+#[test]
+fn identify_all_test() -> Result<()> {
+    let src = MacAddr::new(0xec, 0x63, 0xd7, 0x5d, 0x5d, 0x22);
+    let req = PacketIdentReq::new_identify_all(src);
+    let data = req.to_vec();
+    assert_eq!(&data[..6], &[0x01, 0x0e, 0xcf, 0x00, 0x00, 0x00]);
+    assert_eq!(&data[26..], &[0xff, 0xff, 0x00, 0x00]);
+
+    let parsed = PacketIdentReq::try_from(data.as_slice())?;
+    assert!(parsed.filter().is_identify_all());
+    let device = PacketIdentResp::try_from(get_ident_resp().as_slice())?;
+    assert!(parsed.matches(&device));
+    Ok(())
+}
+
+#[test]
+fn filter_builder_test() -> Result<()> {
+    let name = NameOfStation::new("bb-abci.111")?;
+    let filter = IdentFilter::identify_all()
+        .and_name_of_station(&name)
+        .and_device_id(DeviceId::new(0x002a, 0x0000));
+    assert_eq!(
+        filter.options(),
+        &[
+            name.to_option(),
+            OptionAndSubValue::DeviceId(DeviceId::new(0x002a, 0x0000))
+        ]
+    );
+    // a later filter of the same option replaces the earlier one
+    let filter = IdentFilter::by_alias("port-001.a").and_alias("port-002.b");
+    assert_eq!(
+        filter.options(),
+        &[OptionAndSubValue::AliasName("port-002.b".as_bytes().into())]
+    );
+
+    let src = MacAddr::new(0xec, 0x63, 0xd7, 0x5d, 0x5d, 0x22);
+    let filter = IdentFilter::by_role(DeviceRole::IO_CONTROLLER)
+        .and_ip(device_ip())
+        .and_device_instance(0x00, 0x01);
+    let req = PacketIdentReq::new_by_filter(src, &filter);
+    let parsed = PacketIdentReq::try_from(req.to_vec().as_slice())?;
+    assert_eq!(parsed.filter(), filter);
+    assert_eq!(parsed, req);
+
+    let dest = MacAddr::new(0x00, 0x1c, 0x06, 0x11, 0x42, 0x02);
+    let mut req = PacketIdentReq::new_unicast(src, dest);
+    req.append_filter(&filter);
+    assert_eq!(req.destination, dest);
+    assert_eq!(req.filter(), filter);
+    Ok(())
+}
+
+#[test]
+fn filter_matches_test() -> Result<()> {
+    let device = PacketIdentResp::try_from(get_ident_resp().as_slice())?;
+    let name = NameOfStation::new("bb-abci.111")?;
+    let matched = [
+        IdentFilter::by_name_of_station(&name),
+        IdentFilter::default().and(OptionAndSubValue::NameOfStation(
+            "BB-ABCI.111".as_bytes().into(),
+        )),
+        IdentFilter::by_device_id(DeviceId::new(0x002a, 0x0000)),
+        IdentFilter::by_role(DeviceRole::IO_CONTROLLER),
+        IdentFilter::by_ip(device_ip()),
+        IdentFilter::by_name_of_station(&name)
+            .and_device_id(DeviceId::new(0x002a, 0x0000))
+            .and_role(DeviceRole::IO_CONTROLLER)
+            .and_ip(device_ip()),
+    ];
+    for filter in matched {
+        assert!(filter.matches(&device), "{:?}", filter);
+    }
+
+    let other_ip = InnerIpAddr::new_by_ipv4(
+        Ipv4Addr::new(192, 168, 199, 246),
+        Ipv4Addr::new(255, 255, 255, 0),
+        Ipv4Addr::new(192, 168, 199, 254),
+    );
+    let unmatched = [
+        IdentFilter::by_name_of_station(&NameOfStation::new("other")?),
+        IdentFilter::by_alias("port-001.chassis"),
+        IdentFilter::by_device_id(DeviceId::new(0x002a, 0x0001)),
+        IdentFilter::by_role(DeviceRole::IO_DEVICE),
+        IdentFilter::by_ip(other_ip),
+        // the device does not report an instance
+        IdentFilter::by_device_instance(0x00, 0x01),
+        IdentFilter::by_name_of_station(&name).and_role(DeviceRole::IO_DEVICE),
+    ];
+    for filter in unmatched {
+        assert!(!filter.matches(&device), "{:?}", filter);
+    }
+
+    // the captured request asks for the type of station of the device
+    let req = PacketIdentReq::try_from(get_ident_req().as_slice())?;
+    assert!(req.matches(&device));
+    Ok(())
+}
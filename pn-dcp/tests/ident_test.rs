@@ -2,10 +2,13 @@ mod comm;
 
 use anyhow::Result;
 use comm::*;
-use pn_dcp::block::{BlockCommon, BlockIp};
+use pn_dcp::block::{BlockCommon, BlockFullIpSuite, BlockIp};
 use pn_dcp::options::IpBlockInfo;
-use pn_dcp::options::OptionAndSub::{AliasName, DeviceOptions, DeviceRole, IpAddr, MarAddr};
-use pn_dcp::options::{DeviceOptionsBuilder, InnerIpAddr, OptionAndSub, OptionAndSubValue};
+use pn_dcp::options::OptionAndSub::{AliasName, DeviceOptions, IpAddr, MacAddr as MarAddr};
+use pn_dcp::options::{
+    DeviceId, DeviceInitiative, DeviceOptionsBuilder, DeviceRole, InnerFullIpSuite, InnerIpAddr,
+    NameOfStation, OptionAndSub, OptionAndSubValue, StandardGatewayValue,
+};
 use pn_dcp::packet::ident_req::PacketIdentReq;
 use pn_dcp::packet::ident_resp::PacketIdentResp;
 use pnet::util::MacAddr;
@@ -49,7 +52,7 @@ fn ident_resp_test() -> Result<()> {
         .append_option(OptionAndSub::ManufacturerSpecific)
         .append_option(OptionAndSub::NameOfStation)
         .append_option(OptionAndSub::DeviceId)
-        .append_option(DeviceRole)
+        .append_option(OptionAndSub::DeviceRole)
         .append_option(DeviceOptions)
         .append_option(AliasName)
         .append_option(MarAddr)
@@ -61,9 +64,9 @@ fn ident_resp_test() -> Result<()> {
         .append_to_ident_resp_default(&mut resp);
     let name = OptionAndSubValue::NameOfStation("bb-abci.111".as_bytes().into());
     name.clone().append_to_ident_resp_default(&mut resp);
-    let device_id = OptionAndSubValue::DeviceId([0x00, 0x2a], [0x00, 0x00]);
+    let device_id = DeviceId::new(0x002a, 0x0000).to_option();
     device_id.clone().append_to_ident_resp_default(&mut resp);
-    let device_role = OptionAndSubValue::DeviceRole(0x02, 0x00);
+    let device_role = DeviceRole::IO_CONTROLLER.to_option();
     device_role.clone().append_to_ident_resp_default(&mut resp);
     let ip = InnerIpAddr::new_by_ipv4(
         Ipv4Addr::from(0xc0a8c7f5),
@@ -89,6 +92,210 @@ fn ident_resp_test() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn ident_resp_full_ip_suite_test() -> Result<()> {
+    let data = get_ident_resp();
+    let src = get_src_array(data.as_slice()).unwrap();
+    let dest = get_destination_array(data.as_slice()).unwrap();
+    let mut resp = PacketIdentResp::new(init_mac_by_array(src), init_mac_by_array(dest));
+    let ip = InnerIpAddr::new_by_ipv4(
+        Ipv4Addr::from(0xc0a8c7f5),
+        Ipv4Addr::from(0xffffff00),
+        Ipv4Addr::from(0xc0a8c7fe),
+    );
+    let suite = InnerFullIpSuite::new_by_ipv4(ip, [Ipv4Addr::from(0xc0a8c7fe); 4]);
+    suite
+        .clone()
+        .append_to_ident_resp(&mut resp, IpBlockInfo::IpSetByDhcp);
+
+    let parsed = PacketIdentResp::try_from(resp.to_vec().as_slice())?;
+    assert_eq!(
+        parsed.block_full_ip_suite()?,
+        BlockFullIpSuite::new(suite, IpBlockInfo::IpSetByDhcp)
+    );
+    assert_eq!(parsed, resp);
+    Ok(())
+}
+
+#[test]
+fn ident_req_mac_test() -> Result<()> {
+    let src = MacAddr::new(0xec, 0x63, 0xd7, 0x5d, 0x5d, 0x22);
+    let target = MacAddr::new(0x00, 0x1c, 0x06, 0x11, 0x42, 0x02);
+    let mut req = PacketIdentReq::new(src);
+    req.append_block_by_option(OptionAndSubValue::MacAddr(target));
+
+    let parsed = PacketIdentReq::try_from(req.to_vec().as_slice())?;
+    assert_eq!(parsed.options(), vec![OptionAndSubValue::MacAddr(target)]);
+    assert_eq!(parsed, req);
+
+    #[allow(deprecated)]
+    let alias = OptionAndSub::MarAddr;
+    assert_eq!(alias, OptionAndSub::MacAddr);
+    assert_eq!(OptionAndSub::new(1, 1)?, OptionAndSub::MacAddr);
+    Ok(())
+}
+
+#[test]
+fn ident_req_alias_test() -> Result<()> {
+    let src = MacAddr::new(0xec, 0x63, 0xd7, 0x5d, 0x5d, 0x22);
+    let alias = OptionAndSubValue::alias_name("port-001", "chassis");
+    assert_eq!(
+        alias,
+        OptionAndSubValue::AliasName("port-001.chassis".as_bytes().into())
+    );
+
+    let req = PacketIdentReq::new_by_alias(src, "port-001.chassis");
+    let data = req.to_vec();
+    assert_eq!(&data[26..30], &[0x02, 0x06, 0x00, 0x10]);
+    assert_eq!(data.len(), 26 + 20);
+
+    let parsed = PacketIdentReq::try_from(data.as_slice())?;
+    assert_eq!(parsed.options(), vec![alias]);
+    assert_eq!(parsed, req);
+    Ok(())
+}
+
+#[test]
+fn ident_resp_device_instance_test() -> Result<()> {
+    let data = get_ident_resp();
+    let src = get_src_array(data.as_slice()).unwrap();
+    let dest = get_destination_array(data.as_slice()).unwrap();
+    let mut resp = PacketIdentResp::new(init_mac_by_array(src), init_mac_by_array(dest));
+    let options = vec![
+        OptionAndSubValue::DeviceInstance(0x00, 0x01),
+        OptionAndSubValue::OEMDeviceId(DeviceId::new(0x002a, 0x010d)),
+        OptionAndSubValue::StandardGateway(StandardGatewayValue::Gateway),
+        OptionAndSubValue::RSIProperties(0x0003),
+    ];
+    for option in options.iter() {
+        option.clone().append_to_ident_resp_default(&mut resp);
+    }
+    let bytes = resp.to_vec();
+    assert_eq!(
+        &bytes[26..],
+        &[
+            0x02, 0x07, 0x00, 0x04, 0x00, 0x00, 0x00, 0x01, 0x02, 0x08, 0x00, 0x06, 0x00, 0x00,
+            0x00, 0x2a, 0x01, 0x0d, 0x02, 0x09, 0x00, 0x04, 0x00, 0x00, 0x00, 0x01, 0x02, 0x0a,
+            0x00, 0x04, 0x00, 0x00, 0x00, 0x03
+        ]
+    );
+
+    let parsed = PacketIdentResp::try_from(bytes.as_slice())?;
+    let parsed_options: Vec<_> = parsed
+        .block_commons()
+        .iter()
+        .map(|x| x.option().clone())
+        .collect();
+    assert_eq!(parsed_options, options);
+    assert_eq!(parsed, resp);
+    assert_eq!(OptionAndSub::new(2, 10)?, OptionAndSub::RSIProperties);
+    Ok(())
+}
+
+#[test]
+fn ident_resp_reserved_standard_gateway_test() -> Result<()> {
+    let data = get_ident_resp();
+    let mut resp = PacketIdentResp::try_from(data.as_slice())?;
+    let count = resp.block_commons().len();
+    OptionAndSubValue::StandardGateway(StandardGatewayValue::from(0x0002))
+        .append_to_ident_resp_default(&mut resp);
+
+    let parsed = PacketIdentResp::try_from(resp.to_vec().as_slice())?;
+    assert_eq!(parsed.block_commons().len(), count + 1);
+    assert_eq!(
+        parsed.block_commons().last().unwrap().option(),
+        &OptionAndSubValue::StandardGateway(StandardGatewayValue::UnSupport(0x0002))
+    );
+    assert_eq!(parsed, resp);
+    Ok(())
+}
+
+#[test]
+fn ident_resp_device_initiative_test() -> Result<()> {
+    let data = get_ident_resp();
+    let req = PacketIdentResp::try_from(data.as_slice())?;
+    assert_eq!(req.device_initiative(), None);
+
+    let src = get_src_array(data.as_slice()).unwrap();
+    let dest = get_destination_array(data.as_slice()).unwrap();
+    let mut resp = PacketIdentResp::new(init_mac_by_array(src), init_mac_by_array(dest));
+    DeviceInitiative::new(true)
+        .to_option()
+        .append_to_ident_resp_default(&mut resp);
+    let bytes = resp.to_vec();
+    assert_eq!(
+        &bytes[26..],
+        &[0x06, 0x01, 0x00, 0x04, 0x00, 0x00, 0x00, 0x01]
+    );
+
+    let parsed = PacketIdentResp::try_from(bytes.as_slice())?;
+    assert!(parsed.device_initiative().unwrap().issues_hello());
+    assert!(!DeviceInitiative::new(false).issues_hello());
+    assert_eq!(parsed, resp);
+
+    #[allow(deprecated)]
+    let alias = OptionAndSub::DevicecInitiative;
+    assert_eq!(alias, OptionAndSub::DeviceInitiative);
+    Ok(())
+}
+
+#[test]
+fn device_role_test() -> Result<()> {
+    let role = DeviceRole::IO_DEVICE | DeviceRole::PN_SUPERVISOR;
+    assert!(role.contains(DeviceRole::IO_DEVICE));
+    assert!(!role.contains(DeviceRole::IO_CONTROLLER));
+    assert_eq!(role.to_string(), "IO-Device | PN-Supervisor");
+    assert_eq!(DeviceRole::from_bits(0).to_string(), "None");
+    assert_eq!(
+        DeviceRole::from_bits(0x32).to_string(),
+        "IO-Controller | 0x30"
+    );
+
+    let data = get_ident_resp();
+    let src = get_src_array(data.as_slice()).unwrap();
+    let dest = get_destination_array(data.as_slice()).unwrap();
+    let mut resp = PacketIdentResp::new(init_mac_by_array(src), init_mac_by_array(dest));
+    DeviceRole::from_bits(0x32)
+        .to_option()
+        .append_to_ident_resp_default(&mut resp);
+    let bytes = resp.to_vec();
+    assert_eq!(
+        &bytes[26..],
+        &[0x02, 0x04, 0x00, 0x04, 0x00, 0x00, 0x32, 0x00]
+    );
+    let parsed = PacketIdentResp::try_from(bytes.as_slice())?;
+    assert_eq!(
+        parsed.block_commons()[0].option(),
+        &DeviceRole::from_bits(0x32).to_option()
+    );
+    assert_eq!(parsed, resp);
+    Ok(())
+}
+
+#[test]
+fn device_id_test() -> Result<()> {
+    let id = DeviceId::new(0x002a, 0x0401);
+    assert_eq!(id.to_string(), "0x002A/0x0401");
+    assert_eq!("0x002A/0x0401".parse::<DeviceId>()?, id);
+    assert_eq!("2a/401".parse::<DeviceId>()?, id);
+    assert!("0x002A".parse::<DeviceId>().is_err());
+    assert!("0x002A/0xZZ".parse::<DeviceId>().is_err());
+    assert!(DeviceId::new(0x002a, 0x0402) > id);
+    assert!(DeviceId::new(0x0001, 0xffff) < id);
+
+    let src = MacAddr::new(0xec, 0x63, 0xd7, 0x5d, 0x5d, 0x22);
+    let mut req = PacketIdentReq::new(src);
+    req.append_block_by_option(id.to_option());
+    let data = req.to_vec();
+    assert_eq!(
+        &data[26..],
+        &[0x02, 0x03, 0x00, 0x04, 0x00, 0x2a, 0x04, 0x01]
+    );
+    let parsed = PacketIdentReq::try_from(data.as_slice())?;
+    assert_eq!(parsed.options(), vec![OptionAndSubValue::DeviceId(id)]);
+    Ok(())
+}
+
 #[test]
 fn ident_req_ext_test() -> Result<()> {
     let ident_req_data = get_ident_req_ext();
@@ -106,3 +313,50 @@ fn ident_resp_ext_test() -> Result<()> {
     assert_eq!(resp, resp_ext);
     Ok(())
 }
+
+#[test]
+fn ident_req_by_name_of_station_test() -> Result<()> {
+    let src = MacAddr::new(0x00, 0x0c, 0x29, 0x6d, 0x0f, 0x2e);
+    let name = NameOfStation::new("bb-abci.111")?;
+    let req = PacketIdentReq::new_by_name_of_station(src, &name);
+    let data = req.to_vec();
+    assert_eq!(&data[26..30], &[0x02, 0x02, 0x00, 0x0b]);
+    assert_eq!(&data[30..41], "bb-abci.111".as_bytes());
+
+    let parsed = PacketIdentReq::try_from(data.as_slice())?;
+    assert_eq!(parsed.options(), vec![name.to_option()]);
+    assert_eq!(parsed, req);
+    Ok(())
+}
+
+#[test]
+fn ident_resp_name_of_station_unicode_test() -> Result<()> {
+    let src = MacAddr::new(0x00, 0x0c, 0x29, 0x6d, 0x0f, 0x2e);
+    let dest = MacAddr::new(0x00, 0x0c, 0x29, 0x6d, 0x0f, 0x2f);
+    let name = NameOfStation::from_unicode("förderband-1")?;
+    let mut resp = PacketIdentResp::new(src, dest);
+    resp.append_block_common_default(name.to_option());
+
+    let parsed = PacketIdentResp::try_from(resp.to_vec().as_slice())?;
+    let parsed_name = parsed.name_of_station()?;
+    assert_eq!(parsed_name.as_str(), "xn-frderband-1-rfb");
+    assert_eq!(parsed_name.to_unicode(), "förderband-1");
+    assert!(PacketIdentResp::new(src, dest).name_of_station().is_err());
+    Ok(())
+}
+
+#[test]
+fn ident_resp_raw_name_of_station_test() -> Result<()> {
+    let src = MacAddr::new(0x00, 0x0c, 0x29, 0x6d, 0x0f, 0x2e);
+    let dest = MacAddr::new(0x00, 0x0c, 0x29, 0x6d, 0x0f, 0x2f);
+    let mut resp = PacketIdentResp::new(src, dest);
+    resp.append_block_common_default(OptionAndSubValue::NameOfStation("PLC-1".as_bytes().into()));
+
+    let parsed = PacketIdentResp::try_from(resp.to_vec().as_slice())?;
+    assert_eq!(parsed.raw_name_of_station()?.as_ref(), "PLC-1".as_bytes());
+    assert!(parsed.name_of_station().is_err());
+    assert!(PacketIdentResp::new(src, dest)
+        .raw_name_of_station()
+        .is_err());
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use pn_dcp::options::{InnerFullIpSuite, InnerIpAddr, IpSuiteError};
+use std::net::Ipv4Addr;
+
+fn ip(ip: &str, mask: &str, gateway: &str) -> InnerIpAddr {
+    InnerIpAddr::new_by_ipv4(
+        ip.parse().unwrap(),
+        mask.parse().unwrap(),
+        gateway.parse().unwrap(),
+    )
+}
+
+#[test]
+fn ip_suite_valid_test() {
+    for suite in [
+        ip("192.168.199.245", "255.255.255.0", "192.168.199.254"),
+        ip("192.168.199.245", "255.255.255.0", "0.0.0.0"),
+        ip("192.168.199.245", "255.255.255.0", "192.168.199.245"),
+        ip("10.1.2.3", "255.0.0.0", "10.255.255.254"),
+        ip("172.16.0.1", "255.255.255.252", "172.16.0.2"),
+        ip("0.0.0.0", "0.0.0.0", "0.0.0.0"),
+    ] {
+        assert_eq!(suite.validate(), Ok(()), "{:?}", suite);
+    }
+    let suite = ip("192.168.199.245", "255.255.240.0", "192.168.192.1");
+    assert_eq!(suite.prefix_len(), Some(20));
+    assert_eq!(suite.network(), Ipv4Addr::new(192, 168, 192, 0));
+    assert_eq!(suite.broadcast(), Ipv4Addr::new(192, 168, 207, 255));
+    assert!(
+        InnerFullIpSuite::new_by_ipv4(suite, [Ipv4Addr::UNSPECIFIED; 4])
+            .validate()
+            .is_ok()
+    );
+}
+
+#[test]
+fn ip_suite_invalid_test() {
+    let cases = [
+        (
+            ip("192.168.199.245", "255.0.255.0", "0.0.0.0"),
+            IpSuiteError::NonContiguousMask(Ipv4Addr::new(255, 0, 255, 0)),
+        ),
+        (
+            ip("192.168.199.245", "255.255.255.255", "0.0.0.0"),
+            IpSuiteError::UnusableMask {
+                mask: Ipv4Addr::BROADCAST,
+                prefix: 32,
+            },
+        ),
+        (
+            ip("192.168.199.245", "0.0.0.0", "0.0.0.0"),
+            IpSuiteError::UnusableMask {
+                mask: Ipv4Addr::UNSPECIFIED,
+                prefix: 0,
+            },
+        ),
+        (
+            ip("224.0.0.1", "255.255.255.0", "0.0.0.0"),
+            IpSuiteError::InvalidAddress(Ipv4Addr::new(224, 0, 0, 1)),
+        ),
+        (
+            ip("192.168.199.0", "255.255.255.0", "0.0.0.0"),
+            IpSuiteError::NetworkAddress {
+                ip: Ipv4Addr::new(192, 168, 199, 0),
+                network: Ipv4Addr::new(192, 168, 199, 0),
+            },
+        ),
+        (
+            ip("192.168.199.255", "255.255.255.0", "0.0.0.0"),
+            IpSuiteError::BroadcastAddress {
+                ip: Ipv4Addr::new(192, 168, 199, 255),
+                broadcast: Ipv4Addr::new(192, 168, 199, 255),
+            },
+        ),
+        (
+            ip("192.168.199.245", "255.255.255.0", "192.168.1.1"),
+            IpSuiteError::GatewayOutsideSubnet {
+                gateway: Ipv4Addr::new(192, 168, 1, 1),
+                network: Ipv4Addr::new(192, 168, 199, 0),
+                mask: Ipv4Addr::new(255, 255, 255, 0),
+            },
+        ),
+        (
+            ip("192.168.199.245", "255.255.255.0", "192.168.199.0"),
+            IpSuiteError::GatewayNetworkAddress(Ipv4Addr::new(192, 168, 199, 0)),
+        ),
+        (
+            ip("192.168.199.245", "255.255.255.0", "192.168.199.255"),
+            IpSuiteError::GatewayBroadcastAddress(Ipv4Addr::new(192, 168, 199, 255)),
+        ),
+    ];
+    for (suite, err) in cases {
+        assert_eq!(suite.validate(), Err(err), "{:?}", suite);
+    }
+    let err = InnerIpAddr::try_new_by_ipv4(
+        Ipv4Addr::new(192, 168, 199, 245),
+        Ipv4Addr::new(255, 255, 255, 0),
+        Ipv4Addr::new(10, 0, 0, 1),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "gateway 10.0.0.1 is outside the subnet 192.168.199.0/255.255.255.0"
+    );
+}
@@ -0,0 +1,131 @@
+use anyhow::Result;
+use pn_dcp::options::{NameOfStation, NameOfStationError, OptionAndSubValue};
+
+#[test]
+fn name_of_station_valid_test() -> Result<()> {
+    for name in [
+        "bb-abci.111",
+        "plc1",
+        "io-device.line-2.hall3",
+        "port-01",
+        "port-001a",
+    ] {
+        let nos = NameOfStation::new(name)?;
+        assert_eq!(nos.as_str(), name);
+        assert_eq!(nos.to_string(), name);
+        assert_eq!(
+            nos.to_option(),
+            OptionAndSubValue::NameOfStation(name.as_bytes().into())
+        );
+    }
+    let label = "a".repeat(63);
+    let name = [label.as_str(); 3].join(".");
+    assert_eq!(name.len(), 191);
+    assert!(NameOfStation::new(&name).is_ok());
+    Ok(())
+}
+
+#[test]
+fn name_of_station_invalid_test() {
+    let too_long = vec!["a".repeat(60); 4].join(".") + ".a";
+    let long_label = "a".repeat(64);
+    let cases = [
+        ("", NameOfStationError::Empty),
+        (too_long.as_str(), NameOfStationError::TooLong(245)),
+        ("a..b", NameOfStationError::EmptyLabel),
+        (".ab", NameOfStationError::EmptyLabel),
+        ("ab.", NameOfStationError::EmptyLabel),
+        (
+            long_label.as_str(),
+            NameOfStationError::LabelTooLong {
+                label: long_label.clone(),
+                len: 64,
+            },
+        ),
+        (
+            "Plc1",
+            NameOfStationError::InvalidChar {
+                ch: 'P',
+                position: 0,
+            },
+        ),
+        (
+            "plc_1",
+            NameOfStationError::InvalidChar {
+                ch: '_',
+                position: 3,
+            },
+        ),
+        (
+            "ab.-cd",
+            NameOfStationError::LeadingHyphen {
+                label: "-cd".to_string(),
+            },
+        ),
+        (
+            "ab-.cd",
+            NameOfStationError::TrailingHyphen {
+                label: "ab-".to_string(),
+            },
+        ),
+        ("port-001", NameOfStationError::PortName),
+        ("port-001-00002", NameOfStationError::PortName),
+        ("port-001.chassis", NameOfStationError::PortName),
+        ("192.168.0.1", NameOfStationError::IpAddress),
+        ("999.1.02.3", NameOfStationError::IpAddress),
+    ];
+    for (name, err) in cases {
+        assert_eq!(NameOfStation::new(name), Err(err), "{}", name);
+    }
+}
+
+#[test]
+fn name_of_station_parse_test() -> Result<()> {
+    let nos: NameOfStation = "bb-abci.111".parse()?;
+    assert_eq!(
+        nos,
+        NameOfStation::try_from(pn_dcp::comm::BytesWrap::from("bb-abci.111".as_bytes()))?
+    );
+    assert!("S7-200 SMART".parse::<NameOfStation>().is_err());
+    let err = NameOfStation::new("port-123").unwrap_err();
+    assert!(err.to_string().contains("port-xyz"));
+    assert_eq!(
+        NameOfStationError::TooLong(245).to_string(),
+        "name of station is 245 bytes long, at most 240 are allowed"
+    );
+    Ok(())
+}
+
+#[test]
+fn name_of_station_unicode_test() -> Result<()> {
+    let cases = [
+        ("förderband-1", "xn-frderband-1-rfb"),
+        ("München.halle-2", "xn-mnchen-3ya.halle-2"),
+        ("ü", "xn-tda"),
+        ("line.straße", "line.xn-strae-oqa"),
+        ("bb-abci.111", "bb-abci.111"),
+    ];
+    for (unicode, wire) in cases {
+        let nos = NameOfStation::from_unicode(unicode)?;
+        assert_eq!(nos.as_str(), wire);
+        assert_eq!(nos.to_unicode(), unicode.to_lowercase());
+        assert_eq!(
+            NameOfStation::new(wire)?.to_unicode(),
+            unicode.to_lowercase()
+        );
+    }
+    // positions refer to the encoded name
+    assert!(matches!(
+        NameOfStation::from_unicode("förder_band"),
+        Err(NameOfStationError::InvalidChar { ch: '_', .. })
+    ));
+    // not valid punycode, kept as it is
+    assert_eq!(NameOfStation::new("xn-9")?.to_unicode(), "xn-9");
+    // decodes, but to a string from_unicode would not encode to this label
+    assert_eq!(NameOfStation::new("xn-a0l")?.to_unicode(), "xn-a0l");
+    assert_eq!(
+        NameOfStation::new("plc.xn-a1k.xn-frderband-1-rfb")?.to_unicode(),
+        "plc.xn-a1k.förderband-1"
+    );
+    Ok(())
+}
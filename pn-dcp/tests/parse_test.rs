@@ -0,0 +1,212 @@
+mod comm;
+
+use anyhow::Result;
+use comm::*;
+use pn_dcp::error::DcpError;
+use pn_dcp::options::OptionAndSub;
+use pn_dcp::packet::dcp_packet::DcpPacket;
+use pn_dcp::packet::get_req::PacketGetReq;
+use pn_dcp::packet::get_resp::PacketGetResp;
+use pn_dcp::packet::ident_req::PacketIdentReq;
+use pn_dcp::packet::ident_resp::PacketIdentResp;
+use pn_dcp::packet::set_req::PacketSetReq;
+use pn_dcp::packet::set_resp::PacketSetResp;
+use pn_dcp::packet::{DcpEncode, DcpParse};
+use pn_dcp::parse::{ParseOptions, ParseWarning};
+
+#[test]
+fn strict_fixtures_test() -> Result<()> {
+    let frames = [
+        get_hello_req(),
+        get_hello_resp(),
+        get_ident_req(),
+        get_ident_resp(),
+        get_get_req(),
+        get_get_resp(),
+        get_get_resp_full_ip_suite(),
+        get_set_req(),
+        get_set_req_multi(),
+        get_set_resp(),
+        get_set_resp_multi(),
+    ];
+    for frame in frames {
+        let parsed = DcpPacket::parse_with(frame.as_slice(), ParseOptions::strict())?;
+        assert!(parsed.warnings.is_empty());
+        assert_eq!(parsed.packet, DcpPacket::try_from(frame.as_slice())?);
+    }
+    Ok(())
+}
+
+#[test]
+fn length_mismatch_test() -> Result<()> {
+    // ip block with two extra bytes
+    let mut data = get_set_req();
+    data[25] = 0x14;
+    data[29] = 0x10;
+    data.extend_from_slice(&[0x00, 0x00]);
+    let warning = ParseWarning::LengthMismatch {
+        option: OptionAndSub::IpAddr,
+        expected: 12,
+        got: 14,
+    };
+
+    let parsed = PacketSetReq::parse_with(data.as_slice(), ParseOptions::lenient())?;
+    assert_eq!(parsed.warnings, vec![warning.clone()]);
+    assert_eq!(
+        parsed.packet.blocks(),
+        PacketSetReq::try_from(get_set_req().as_slice())?.blocks()
+    );
+    assert!(PacketSetReq::try_from(data.as_slice()).is_ok());
+    assert_eq!(
+        PacketSetReq::parse_with(data.as_slice(), ParseOptions::strict()),
+        Err(DcpError::Strict(warning))
+    );
+    Ok(())
+}
+
+#[test]
+fn non_zero_padding_test() -> Result<()> {
+    let mut data = get_get_resp();
+    *data.last_mut().unwrap() = 0x01;
+    let warning = ParseWarning::NonZeroPadding {
+        offset: 25,
+        value: 0x01,
+    };
+    let parsed = PacketGetResp::parse_with(data.as_slice(), ParseOptions::lenient())?;
+    assert_eq!(parsed.warnings, vec![warning.clone()]);
+    assert_eq!(
+        PacketGetResp::parse_with(data.as_slice(), ParseOptions::strict()),
+        Err(DcpError::Strict(warning))
+    );
+    Ok(())
+}
+
+#[test]
+fn unknown_block_info_test() -> Result<()> {
+    let mut data = get_ident_resp();
+    data[31] = 0x05;
+    let warning = ParseWarning::UnknownBlockInfo {
+        option: OptionAndSub::ManufacturerSpecific,
+        info: [0x00, 0x05],
+    };
+    let parsed = PacketIdentResp::parse_with(data.as_slice(), ParseOptions::lenient())?;
+    assert_eq!(parsed.warnings, vec![warning.clone()]);
+    assert_eq!(
+        PacketIdentResp::parse_with(data.as_slice(), ParseOptions::strict()),
+        Err(DcpError::Strict(warning))
+    );
+    Ok(())
+}
+
+#[test]
+fn unexpected_block_test() -> Result<()> {
+    let mut data = get_ident_resp();
+    data[25] += 8;
+    data.extend_from_slice(&[0x05, 0x04, 0x00, 0x03, 0x02, 0x02, 0x00, 0x00]);
+    let warning = ParseWarning::UnexpectedBlock(OptionAndSub::Response);
+
+    let parsed = PacketIdentResp::parse_with(data.as_slice(), ParseOptions::lenient())?;
+    assert_eq!(parsed.warnings, vec![warning.clone()]);
+    assert_eq!(
+        parsed.packet.block_commons(),
+        PacketIdentResp::try_from(get_ident_resp().as_slice())?.block_commons()
+    );
+    assert_eq!(
+        PacketIdentResp::parse_with(data.as_slice(), ParseOptions::strict()),
+        Err(DcpError::Strict(warning))
+    );
+    Ok(())
+}
+
+#[test]
+fn get_req_strict_test() -> Result<()> {
+    let mut data = get_get_req();
+    data[25] += 3;
+    data.extend_from_slice(&[0x05, 0x01, 0x01]);
+    let unexpected = ParseWarning::UnexpectedBlock(OptionAndSub::StartTransaction);
+    let padding = ParseWarning::NonZeroPadding {
+        offset: 6,
+        value: 0x01,
+    };
+
+    let parsed = PacketGetReq::parse_with(data.as_slice(), ParseOptions::lenient())?;
+    assert_eq!(parsed.warnings, vec![unexpected.clone(), padding]);
+    assert_eq!(
+        parsed.packet.options(),
+        PacketGetReq::try_from(get_get_req().as_slice())?.options()
+    );
+    assert_eq!(
+        PacketGetReq::parse_with(data.as_slice(), ParseOptions::strict()),
+        Err(DcpError::Strict(unexpected))
+    );
+    Ok(())
+}
+
+#[test]
+fn set_resp_unexpected_block_test() -> Result<()> {
+    let mut data = get_set_resp();
+    data[25] += 6;
+    data.extend_from_slice(&[0x02, 0x02, 0x00, 0x02, 0x61, 0x62]);
+    let warning = ParseWarning::UnexpectedBlock(OptionAndSub::NameOfStation);
+
+    let parsed = PacketSetResp::parse_with(data.as_slice(), ParseOptions::lenient())?;
+    assert_eq!(parsed.warnings, vec![warning.clone()]);
+    assert_eq!(
+        parsed.packet.blocks(),
+        PacketSetResp::try_from(get_set_resp().as_slice())?.blocks()
+    );
+    assert_eq!(
+        PacketSetResp::parse_with(data.as_slice(), ParseOptions::strict()),
+        Err(DcpError::Strict(warning))
+    );
+    Ok(())
+}
+
+#[test]
+fn lenient_reencode_test() -> Result<()> {
+    let mut data = get_ident_resp();
+    data[25] += 8;
+    data.extend_from_slice(&[0x05, 0x04, 0x00, 0x03, 0x02, 0x02, 0x00, 0x00]);
+    let packet = PacketIdentResp::parse_with(data.as_slice(), ParseOptions::lenient())?.packet;
+    let expected = get_ident_resp();
+    assert_eq!(packet.encoded_len(), expected.len());
+    assert_eq!(packet.to_vec(), expected);
+    let mut buf = [0xffu8; 128];
+    assert_eq!(packet.write_to_slice(&mut buf)?, expected.len());
+    assert_eq!(&buf[..expected.len()], expected.as_slice());
+    assert_eq!(
+        PacketIdentResp::parse_with(packet.to_vec().as_slice(), ParseOptions::strict())?.packet,
+        packet
+    );
+
+    let mut data = get_set_resp();
+    data[25] += 6;
+    data.extend_from_slice(&[0x02, 0x02, 0x00, 0x02, 0x61, 0x62]);
+    let packet = DcpPacket::parse_with(data.as_slice(), ParseOptions::lenient())?.packet;
+    assert_eq!(packet.encoded_len(), get_set_resp().len());
+    assert_eq!(packet.to_vec(), get_set_resp());
+    Ok(())
+}
+
+#[test]
+fn trailing_data_test() -> Result<()> {
+    // zero ethernet padding is fine in strict mode
+    let mut data = get_ident_req();
+    data.extend_from_slice(&[0x00; 4]);
+    let parsed = PacketIdentReq::parse_with(data.as_slice(), ParseOptions::strict())?;
+    assert!(parsed.warnings.is_empty());
+
+    let len = data.len();
+    data.extend_from_slice(&[0xaa, 0xbb]);
+    let warning = ParseWarning::TrailingData {
+        offset: len,
+        len: 2,
+    };
+    let parsed = PacketIdentReq::parse_with(data.as_slice(), ParseOptions::lenient())?;
+    assert_eq!(parsed.warnings, vec![warning.clone()]);
+    assert_eq!(
+        PacketIdentReq::parse_with(data.as_slice(), ParseOptions::strict()),
+        Err(DcpError::Strict(warning))
+    );
+    Ok(())
+}
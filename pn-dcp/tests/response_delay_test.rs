@@ -0,0 +1,73 @@
+mod comm;
+
+use anyhow::Result;
+use comm::*;
+use pn_dcp::packet::ident_req::PacketIdentReq;
+use pn_dcp::packet::response_delay::ResponseDelayFactor;
+use pnet::util::MacAddr;
+use std::collections::HashSet;
+use std::time::Duration;
+
+#[test]
+fn response_delay_factor_test() {
+    let factor = ResponseDelayFactor::from_duration(Duration::from_millis(400));
+    assert_eq!(factor.value(), 40);
+    assert_eq!(factor.to_u8_array(), [0x00, 0x28]);
+    assert_eq!(factor.window(), Duration::from_millis(400));
+    assert_eq!(ResponseDelayFactor::from([0x00, 0x28]), factor);
+
+    // rounded up to whole 10 ms and limited to the spec range
+    let factor = ResponseDelayFactor::from_duration(Duration::from_millis(401));
+    assert_eq!(factor.value(), 41);
+    assert_eq!(
+        ResponseDelayFactor::from_duration(Duration::ZERO),
+        ResponseDelayFactor::NO_DELAY
+    );
+    assert_eq!(
+        ResponseDelayFactor::from_duration(Duration::from_secs(600)),
+        ResponseDelayFactor::MAX
+    );
+    assert_eq!(ResponseDelayFactor::MAX.window(), Duration::from_secs(64));
+}
+
+#[test]
+fn ident_req_response_delay_test() -> Result<()> {
+    let data = get_ident_req();
+    let req = PacketIdentReq::try_from(data.as_slice())?;
+    assert_eq!(req.response_delay_factor(), ResponseDelayFactor::new(0x80));
+    assert_eq!(
+        req.response_delay_factor().window(),
+        Duration::from_millis(1280)
+    );
+
+    let src = MacAddr::new(0xec, 0x63, 0xd7, 0x5d, 0x5d, 0x22);
+    let req = PacketIdentReq::new_identify_all(src).with_response_delay(Duration::from_secs(1));
+    assert_eq!(req.reserved_or_delay, [0x00, 0x64]);
+    let parsed = PacketIdentReq::try_from(req.to_vec().as_slice())?;
+    assert_eq!(parsed.response_delay_factor().value(), 100);
+    Ok(())
+}
+
+#[test]
+fn delay_for_device_test() -> Result<()> {
+    let req = PacketIdentReq::try_from(get_ident_req().as_slice())?;
+    let window = req.response_delay_factor().window();
+    let mut delays = HashSet::new();
+    for last in 0..=255u8 {
+        let mac = MacAddr::new(0x00, 0x1c, 0x06, 0x11, 0x42, last);
+        let delay = req.response_delay_for(mac);
+        assert!(delay < window);
+        assert_eq!(delay.as_nanos() % 10_000_000, 0);
+        // the same device repeats its delay for the same request
+        assert_eq!(req.response_delay_for(mac), delay);
+        delays.insert(delay);
+    }
+    assert!(delays.len() > 64);
+
+    let mac = MacAddr::new(0x00, 0x1c, 0x06, 0x11, 0x42, 0x02);
+    for factor in [0, 1] {
+        let factor = ResponseDelayFactor::new(factor);
+        assert_eq!(factor.delay_for(mac, req.xid), Duration::ZERO);
+    }
+    Ok(())
+}
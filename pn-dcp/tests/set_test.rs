@@ -3,7 +3,12 @@ mod comm;
 use anyhow::Result;
 use comm::*;
 use pn_dcp::block::{BlockResp, BlockSet};
-use pn_dcp::options::{BlockError, BlockQualifier, InnerIpAddr, OptionAndSub};
+use pn_dcp::error::DcpError;
+use pn_dcp::options::{
+    BlockError, BlockQualifier, DhcpClientId, DhcpControl, DhcpValue, InnerFullIpSuite,
+    InnerIpAddr, IpSuiteError, NameOfStation, OptionAndSub, OptionAndSubValue, ResetFactoryMode,
+    SignalValue,
+};
 use pn_dcp::packet::set_req::PacketSetReq;
 use pn_dcp::packet::set_resp::PacketSetResp;
 use std::net::Ipv4Addr;
@@ -31,7 +36,7 @@ fn test_req() -> Result<()> {
 
     assert_eq!(
         get_req.blocks(),
-        BlockSet::new(ip.to_option(), BlockQualifier::SavePermanent)
+        vec![BlockSet::new(ip.to_option(), BlockQualifier::SavePermanent)]
     );
 
     assert_eq!(data, get_req.to_vec());
@@ -39,6 +44,233 @@ fn test_req() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_req_multi() -> Result<()> {
+    let data = get_set_req_multi();
+    let set = PacketSetReq::try_from(data.as_slice())?;
+    let src = get_src_array(data.as_slice()).unwrap();
+    let dest = get_destination_array(data.as_slice()).unwrap();
+
+    let ip = InnerIpAddr::new_by_ipv4(
+        Ipv4Addr::from(0xc0a8c7f5),
+        Ipv4Addr::from(0xffffff00),
+        Ipv4Addr::from(0xc0a8c7fe),
+    );
+    let start = OptionAndSubValue::StartTransaction;
+    let name = OptionAndSubValue::NameOfStation("bb-abci.111".as_bytes().into());
+    let end = OptionAndSubValue::EndTransaction;
+
+    let mut set_req = PacketSetReq::new_empty(init_mac_by_array(src), init_mac_by_array(dest));
+    set_req.set_xid(get_xid(data.as_slice()).unwrap());
+    set_req.append_control(start.clone())?;
+    set_req.append_block(ip.clone().to_option(), BlockQualifier::SavePermanent);
+    set_req.append_block(name.clone(), BlockQualifier::SavePermanent);
+    set_req.append_block(end.clone(), BlockQualifier::SavePermanent);
+
+    assert_eq!(
+        set_req.blocks(),
+        vec![
+            BlockSet::new(start, BlockQualifier::UseTemporary),
+            BlockSet::new(ip.to_option(), BlockQualifier::SavePermanent),
+            BlockSet::new(name, BlockQualifier::SavePermanent),
+            BlockSet::new(end, BlockQualifier::SavePermanent),
+        ]
+    );
+    assert_eq!(set_req.payload_len, data.len() - 26);
+    assert_eq!(data, set_req.to_vec());
+    assert_eq!(set, set_req);
+    Ok(())
+}
+
+#[test]
+fn test_req_multi_rebuild() -> Result<()> {
+    let data = get_set_req_multi();
+    let set = PacketSetReq::try_from(data.as_slice())?;
+    let src = get_src_array(data.as_slice()).unwrap();
+    let dest = get_destination_array(data.as_slice()).unwrap();
+
+    let mut set_req = PacketSetReq::new_empty(init_mac_by_array(src), init_mac_by_array(dest));
+    set_req.set_xid(get_xid(data.as_slice()).unwrap());
+    set_req.append_control(OptionAndSubValue::StartTransaction)?;
+    for block in set.blocks().into_iter().skip(1) {
+        set_req.append_block_set(BlockSet::try_new(
+            block.option().clone(),
+            block.qualifier().clone(),
+        )?);
+    }
+    assert_eq!(
+        set_req.blocks().last(),
+        Some(&BlockSet::new(
+            OptionAndSubValue::EndTransaction,
+            BlockQualifier::SavePermanent
+        ))
+    );
+    assert_eq!(data, set_req.to_vec());
+    assert_eq!(set, set_req);
+    Ok(())
+}
+
+#[test]
+fn test_req_control() -> Result<()> {
+    let src = init_mac_by_array(get_src_array(get_set_req().as_slice()).unwrap());
+    let dest = init_mac_by_array(get_destination_array(get_set_req().as_slice()).unwrap());
+
+    let mut set_req = PacketSetReq::new_empty(src, dest);
+    set_req.append_control(OptionAndSubValue::Signal(SignalValue::FlashOnce))?;
+    set_req.append_control(OptionAndSubValue::ResetFactory(
+        ResetFactoryMode::ResetCommunication,
+    ))?;
+    assert!(set_req
+        .append_control(OptionAndSubValue::NameOfStation("a".as_bytes().into()))
+        .is_err());
+    assert!(BlockSet::try_new(
+        OptionAndSubValue::EndTransaction,
+        BlockQualifier::SavePermanent
+    )
+    .is_ok());
+    assert!(BlockSet::try_new(
+        OptionAndSubValue::StartTransaction,
+        BlockQualifier::UnSupport([0x00, 0x02])
+    )
+    .is_err());
+    assert!(BlockSet::try_new(
+        OptionAndSubValue::Signal(SignalValue::FlashOnce),
+        BlockQualifier::SavePermanent
+    )
+    .is_err());
+
+    let data = set_req.to_vec();
+    assert_eq!(
+        &data[26..],
+        &[0x05, 0x03, 0x00, 0x04, 0x00, 0x00, 0x01, 0x00, 0x05, 0x06, 0x00, 0x02, 0x00, 0x04]
+    );
+    let set = PacketSetReq::try_from(data.as_slice())?;
+    assert_eq!(set, set_req);
+    assert_eq!(
+        set.blocks()[1].option(),
+        &OptionAndSubValue::ResetFactory(ResetFactoryMode::ResetCommunication)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_req_full_ip_suite() -> Result<()> {
+    let src = init_mac_by_array(get_src_array(get_set_req().as_slice()).unwrap());
+    let dest = init_mac_by_array(get_destination_array(get_set_req().as_slice()).unwrap());
+    let ip = InnerIpAddr::new_by_ipv4(
+        Ipv4Addr::from(0xc0a8c7f5),
+        Ipv4Addr::from(0xffffff00),
+        Ipv4Addr::from(0xc0a8c7fe),
+    );
+    let suite = InnerFullIpSuite::new_by_ipv4(ip, [Ipv4Addr::new(8, 8, 4, 4); 4]);
+    let set_req = PacketSetReq::new(
+        src,
+        dest,
+        suite.clone().to_option(),
+        BlockQualifier::SavePermanent,
+    );
+    let data = set_req.to_vec();
+    assert_eq!(set_req.payload_len, 34);
+    assert_eq!(&data[26..30], &[0x01, 0x03, 0x00, 0x1e]);
+    let set = PacketSetReq::try_from(data.as_slice())?;
+    assert_eq!(set.blocks()[0].option(), &suite.to_option());
+    assert_eq!(set, set_req);
+    Ok(())
+}
+
+#[test]
+fn test_req_dhcp() -> Result<()> {
+    let src = init_mac_by_array(get_src_array(get_set_req().as_slice()).unwrap());
+    let dest = init_mac_by_array(get_destination_array(get_set_req().as_slice()).unwrap());
+    let mut set_req = PacketSetReq::new_empty(src, dest);
+    set_req.append_block(
+        DhcpValue::ClientId(DhcpClientId::Arbitrary("plc".as_bytes().into())).to_option(),
+        BlockQualifier::SavePermanent,
+    );
+    set_req.append_block(
+        DhcpValue::Control(DhcpControl::Enable).to_option(),
+        BlockQualifier::SavePermanent,
+    );
+    let data = set_req.to_vec();
+    assert_eq!(
+        &data[26..],
+        &[
+            0x03, 0x3d, 0x00, 0x07, 0x00, 0x01, 0x04, 0x00, 0x70, 0x6c, 0x63, 0x00, 0x03, 0xff,
+            0x00, 0x04, 0x00, 0x01, 0x01, 0x01
+        ]
+    );
+    let set = PacketSetReq::try_from(data.as_slice())?;
+    assert_eq!(set, set_req);
+    Ok(())
+}
+
+#[test]
+fn test_req_dhcp_too_long() -> Result<()> {
+    let src = init_mac_by_array(get_src_array(get_set_req().as_slice()).unwrap());
+    let dest = init_mac_by_array(get_destination_array(get_set_req().as_slice()).unwrap());
+    let err = DcpError::ValueTooLong {
+        option: OptionAndSub::DHCP(12),
+        max: 255,
+        got: 300,
+    };
+    assert_eq!(DhcpValue::host_name(&[b'a'; 300]), Err(err.clone()));
+    assert_eq!(
+        BlockSet::try_new(
+            DhcpValue::HostName(vec![b'a'; 300].into()).to_option(),
+            BlockQualifier::SavePermanent
+        ),
+        Err(err)
+    );
+    assert!(DhcpValue::client_id(&[b'a'; 255]).is_err());
+
+    let name = DhcpValue::host_name(&[b'a'; 255])?;
+    let mut set_req = PacketSetReq::new_empty(src, dest);
+    set_req.append_block_set(BlockSet::try_new(
+        name.to_option(),
+        BlockQualifier::SavePermanent,
+    )?);
+    let set = PacketSetReq::try_from(set_req.to_vec().as_slice())?;
+    assert_eq!(set, set_req);
+    Ok(())
+}
+
+#[test]
+fn test_req_dhcp_unknown() -> Result<()> {
+    let src = init_mac_by_array(get_src_array(get_set_req().as_slice()).unwrap());
+    let dest = init_mac_by_array(get_destination_array(get_set_req().as_slice()).unwrap());
+    assert_eq!(
+        DhcpValue::new(61, vec![0x01, 0x05].into())?,
+        DhcpValue::ClientId(DhcpClientId::Other(vec![0x05].into()))
+    );
+    assert_eq!(
+        DhcpValue::new(255, vec![0x02, 0x01, 0x00].into())?,
+        DhcpValue::Other(255, vec![0x01, 0x00].into())
+    );
+    assert_eq!(
+        DhcpValue::new(66, vec![0x02, 0x61, 0x62].into())?,
+        DhcpValue::Other(66, "ab".as_bytes().into())
+    );
+
+    let values = [
+        DhcpValue::ClientId(DhcpClientId::Other(vec![0x05].into())),
+        DhcpValue::Other(255, vec![0x01, 0x00].into()),
+        DhcpValue::Other(66, "ab".as_bytes().into()),
+    ];
+    let mut set_req = PacketSetReq::new_empty(src, dest);
+    for value in values.iter() {
+        assert!(value.validate().is_err());
+        assert!(
+            BlockSet::try_new(value.clone().to_option(), BlockQualifier::SavePermanent).is_err()
+        );
+        set_req.append_block(value.clone().to_option(), BlockQualifier::SavePermanent);
+    }
+    let data = set_req.to_vec();
+    let set = PacketSetReq::try_from(data.as_slice())?;
+    assert_eq!(set, set_req);
+    assert_eq!(set.to_vec(), data);
+    Ok(())
+}
+
 #[test]
 fn test_resp() -> Result<()> {
     let data = get_set_resp();
@@ -65,6 +297,57 @@ fn test_resp() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_resp_multi() -> Result<()> {
+    let data = get_set_resp_multi();
+    let resp = PacketSetResp::try_from(data.as_slice())?;
+    let req = PacketSetReq::try_from(get_set_req_multi().as_slice())?;
+    let src = get_src_array(data.as_slice()).unwrap();
+
+    let set_resp = PacketSetResp::from_req(init_mac_by_array(src), &req, |block| {
+        match block.option().option() {
+            OptionAndSub::NameOfStation => BlockError::SETNotPossibleByLocalReasons,
+            _ => BlockError::Ok,
+        }
+    });
+    assert_eq!(data, set_resp.to_vec());
+    assert_eq!(resp, set_resp);
+
+    assert_eq!(
+        resp.failures(),
+        vec![BlockResp(
+            OptionAndSub::NameOfStation,
+            BlockError::SETNotPossibleByLocalReasons
+        )]
+    );
+    let matched = resp.match_req(&req)?;
+    let errors: Vec<_> = matched
+        .iter()
+        .map(|(block, error)| (block.option().option(), *error))
+        .collect();
+    assert_eq!(
+        errors,
+        vec![
+            (OptionAndSub::StartTransaction, Some(BlockError::Ok)),
+            (OptionAndSub::IpAddr, Some(BlockError::Ok)),
+            (
+                OptionAndSub::NameOfStation,
+                Some(BlockError::SETNotPossibleByLocalReasons)
+            ),
+            (OptionAndSub::EndTransaction, Some(BlockError::Ok)),
+        ]
+    );
+
+    let single = PacketSetResp::try_from(get_set_resp().as_slice())?;
+    let mut single_req = PacketSetReq::try_from(get_set_req().as_slice())?;
+    assert!(single.match_req(&req).is_err());
+    single_req.set_xid([0, 0, 0, 1]);
+    assert!(single.match_req(&single_req).is_err());
+    single_req.set_xid(single.xid);
+    assert_eq!(single.match_req(&single_req)?[0].1, Some(BlockError::Ok));
+    Ok(())
+}
+
 #[test]
 fn test_get_ext() -> Result<()> {
     let req = PacketSetReq::try_from(get_set_req().as_slice())?;
@@ -77,3 +360,97 @@ fn test_get_ext() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_req_name_of_station() -> Result<()> {
+    let data = get_set_req_multi();
+    let src = init_mac_by_array(get_src_array(data.as_slice()).unwrap());
+    let dest = init_mac_by_array(get_destination_array(data.as_slice()).unwrap());
+    let name = NameOfStation::new("bb-abci.111")?;
+
+    let set_req =
+        PacketSetReq::new_name_of_station(src, dest, &name, BlockQualifier::SavePermanent);
+    assert_eq!(
+        set_req.blocks(),
+        vec![BlockSet::new(
+            name.to_option(),
+            BlockQualifier::SavePermanent
+        )]
+    );
+    let parsed = PacketSetReq::try_from(set_req.to_vec().as_slice())?;
+    assert_eq!(parsed, set_req);
+    Ok(())
+}
+
+#[test]
+fn test_req_ip_validated() -> Result<()> {
+    let data = get_set_req();
+    let set = PacketSetReq::try_from(data.as_slice())?;
+    let src = init_mac_by_array(get_src_array(data.as_slice()).unwrap());
+    let dest = init_mac_by_array(get_destination_array(data.as_slice()).unwrap());
+    let ip = InnerIpAddr::try_new_by_ipv4(
+        Ipv4Addr::from(0xc0a8c7f5),
+        Ipv4Addr::from(0xffffff00),
+        Ipv4Addr::from(0xc0a8c7fe),
+    )?;
+    let mut set_req =
+        PacketSetReq::try_new_ip(src, dest, ip.clone(), BlockQualifier::SavePermanent)?;
+    set_req.set_xid(get_xid(data.as_slice()).unwrap());
+    assert_eq!(set_req, set);
+
+    let invalid = InnerIpAddr::new_by_ipv4(ip.0, ip.1, Ipv4Addr::new(10, 0, 0, 1));
+    assert!(matches!(
+        PacketSetReq::try_new_ip(src, dest, invalid.clone(), BlockQualifier::SavePermanent),
+        Err(DcpError::InvalidIpSuite(
+            IpSuiteError::GatewayOutsideSubnet { .. }
+        ))
+    ));
+    let full = InnerFullIpSuite::new_by_ipv4(invalid, [Ipv4Addr::UNSPECIFIED; 4]);
+    assert!(
+        PacketSetReq::try_new_full_ip_suite(src, dest, full, BlockQualifier::SavePermanent)
+            .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_req_reset_ip() -> Result<()> {
+    let src = init_mac_by_array(get_src_array(get_set_req().as_slice()).unwrap());
+    let dest = init_mac_by_array(get_destination_array(get_set_req().as_slice()).unwrap());
+    let reset = InnerIpAddr::new_by_ipv4(
+        Ipv4Addr::UNSPECIFIED,
+        Ipv4Addr::UNSPECIFIED,
+        Ipv4Addr::UNSPECIFIED,
+    );
+    assert!(reset.is_reset());
+    let set_req = PacketSetReq::try_new_ip(src, dest, reset, BlockQualifier::UseTemporary)?;
+    assert_eq!(
+        set_req,
+        PacketSetReq::new_reset_ip(src, dest, BlockQualifier::UseTemporary)
+    );
+    assert_eq!(&set_req.to_vec()[30..], &[0x00, 0x00].repeat(7));
+    Ok(())
+}
+
+// the ip suite error converts into DcpError, so `?` works next to the other builders
+fn build_ip_req(ip: InnerIpAddr) -> pn_dcp::error::Result<PacketSetReq> {
+    let src = init_mac_by_array(get_src_array(get_set_req().as_slice()).unwrap());
+    let mut set_req = PacketSetReq::try_new_ip(src, src, ip, BlockQualifier::SavePermanent)?;
+    set_req.append_control(OptionAndSubValue::EndTransaction)?;
+    Ok(set_req)
+}
+
+#[test]
+fn test_req_ip_suite_error() {
+    let ip = InnerIpAddr::new_by_ipv4(
+        Ipv4Addr::from(0xc0a8c7f5),
+        Ipv4Addr::from(0xffffff00),
+        Ipv4Addr::new(10, 0, 0, 1),
+    );
+    let err = build_ip_req(ip).unwrap_err();
+    assert!(matches!(
+        err,
+        DcpError::InvalidIpSuite(IpSuiteError::GatewayOutsideSubnet { .. })
+    ));
+    assert!(std::error::Error::source(&err).is_some());
+}
@@ -0,0 +1,146 @@
+mod comm;
+
+use anyhow::Result;
+use comm::*;
+use pn_dcp::error::DcpError;
+use pn_dcp::options::{BlockError, OptionAndSub, OptionAndSubValue, Response};
+use pn_dcp::packet::get_req::PacketGetReq;
+use pn_dcp::packet::get_resp::PacketGetResp;
+use pn_dcp::packet::ident_resp::PacketIdentResp;
+use pn_dcp::packet::set_req::PacketSetReq;
+use pn_dcp::packet::PnDcpTy;
+use pn_dcp::view::DcpFrameView;
+
+#[test]
+fn view_ident_resp_test() -> Result<()> {
+    let data = get_ident_resp();
+    let view = DcpFrameView::new(data.as_slice())?;
+    assert_eq!(view.ty(), PnDcpTy::IdentRespSuc);
+    assert_eq!(view.head().xid, get_xid(data.as_slice()).unwrap());
+    assert_eq!(
+        view.blocks().map(|x| x.option()).collect::<Vec<_>>(),
+        vec![
+            OptionAndSub::ManufacturerSpecific,
+            OptionAndSub::DeviceOptions,
+            OptionAndSub::NameOfStation,
+            OptionAndSub::DeviceId,
+            OptionAndSub::DeviceRole,
+            OptionAndSub::IpAddr,
+        ]
+    );
+    let name = view
+        .blocks()
+        .find(|x| x.option() == OptionAndSub::NameOfStation)
+        .unwrap();
+    assert_eq!(name.block_len(), 13);
+    assert_eq!(name.info(), Some([0x00, 0x00]));
+    assert_eq!(name.value(), "bb-abci.111".as_bytes());
+    // the value is borrowed from the receive buffer
+    assert!(data.as_ptr_range().contains(&name.value().as_ptr()));
+
+    let packet: PacketIdentResp = view.to_packet()?;
+    assert_eq!(packet, PacketIdentResp::try_from(data.as_slice())?);
+    let ip = view.blocks().last().unwrap().to_option_value()?;
+    assert_eq!(ip, packet.block_ip()?.ip().clone().to_option());
+    Ok(())
+}
+
+#[test]
+fn view_get_test() -> Result<()> {
+    let data = get_get_req();
+    let view = DcpFrameView::try_from(data.as_slice())?;
+    let blocks: Vec<_> = view.blocks().collect();
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(blocks[0].option(), OptionAndSub::IpAddr);
+    assert_eq!(blocks[1].option(), OptionAndSub::DHCP(61));
+    assert_eq!(blocks[1].info(), None);
+    assert!(blocks[1].value().is_empty());
+    assert_eq!(
+        view.to_packet::<PacketGetReq>()?,
+        PacketGetReq::try_from(data.as_slice())?
+    );
+
+    let data = get_get_resp();
+    let view = DcpFrameView::new(data.as_slice())?;
+    let resp = view.blocks().nth(1).unwrap();
+    assert_eq!(resp.option(), OptionAndSub::Response);
+    assert_eq!(resp.info(), None);
+    assert_eq!(
+        resp.to_option_value()?,
+        OptionAndSubValue::Response(Response(
+            OptionAndSub::DHCP(61),
+            BlockError::SuboptionUnsuppOrNoDataSetAvail
+        ))
+    );
+    assert_eq!(view.blocks().count(), 2);
+    assert_eq!(
+        view.to_packet::<PacketGetResp>()?,
+        PacketGetResp::try_from(data.as_slice())?
+    );
+    Ok(())
+}
+
+#[test]
+fn view_get_req_padding_test() -> Result<()> {
+    let mut data = get_get_req();
+    data[25] = 0x05;
+    data.push(0x00);
+    let view = DcpFrameView::new(data.as_slice())?;
+    let packet = PacketGetReq::try_from(data.as_slice())?;
+    let options: Vec<_> = view.blocks().map(|x| x.option()).collect();
+    assert_eq!(options, packet.options());
+    assert_eq!(view.to_packet::<PacketGetReq>()?, packet);
+
+    *data.last_mut().unwrap() = 0x01;
+    assert!(DcpFrameView::new(data.as_slice()).is_err());
+    Ok(())
+}
+
+#[test]
+fn view_set_req_test() -> Result<()> {
+    let data = get_set_req_multi();
+    let view = DcpFrameView::new(data.as_slice())?;
+    let set = PacketSetReq::try_from(data.as_slice())?;
+    let values = view
+        .blocks()
+        .map(|x| x.to_option_value())
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(
+        values,
+        set.blocks()
+            .iter()
+            .map(|x| x.option().clone())
+            .collect::<Vec<_>>()
+    );
+    let qualifiers: Vec<_> = view.blocks().map(|x| x.info().unwrap()).collect();
+    assert_eq!(qualifiers, vec![[0, 0], [0, 1], [0, 1], [0, 1]]);
+    assert_eq!(view.to_packet::<PacketSetReq>()?, set);
+    Ok(())
+}
+
+#[test]
+fn view_error_test() {
+    let mut data = get_set_req();
+    data[29] = 0x10;
+    assert_eq!(
+        DcpFrameView::new(data.as_slice()).unwrap_err(),
+        DcpError::BadBlockLength {
+            option: OptionAndSub::IpAddr,
+            declared: 16,
+            available: 14
+        }
+    );
+    let data = get_ident_resp();
+    assert_eq!(
+        DcpFrameView::new(&data[..data.len() - 2]).unwrap_err(),
+        DcpError::Truncated {
+            needed: data.len(),
+            got: data.len() - 2
+        }
+    );
+    let data = get_ident_req();
+    assert!(DcpFrameView::new(data.as_slice())
+        .unwrap()
+        .to_packet::<PacketSetReq>()
+        .is_err());
+}
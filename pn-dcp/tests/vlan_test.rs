@@ -0,0 +1,114 @@
+mod comm;
+
+use anyhow::Result;
+use comm::*;
+use pn_dcp::error::DcpError;
+use pn_dcp::options::NameOfStation;
+use pn_dcp::packet::block_iter::DcpBlocks;
+use pn_dcp::packet::dcp_packet::DcpPacket;
+use pn_dcp::packet::ident_req::PacketIdentReq;
+use pn_dcp::packet::ident_resp::PacketIdentResp;
+use pn_dcp::packet::set_resp::PacketSetResp;
+use pn_dcp::packet::{DcpEncode, DcpHead, DcpParse, VlanTag};
+use pn_dcp::parse::ParseOptions;
+use pn_dcp::view::DcpFrameView;
+use pnet::util::MacAddr;
+
+fn tagged(mut data: Vec<u8>, tag: VlanTag) -> Vec<u8> {
+    let tci = tag.tci().to_be_bytes();
+    data.splice(12..12, [0x81, 0x00, tci[0], tci[1]]);
+    data
+}
+
+#[test]
+fn vlan_tag_test() {
+    let tag = VlanTag::default();
+    assert_eq!((tag.pcp, tag.dei, tag.vid), (6, false, 0));
+    assert_eq!(tag.tci(), 0xc000);
+    let tag = VlanTag::new(5, 100);
+    assert_eq!(tag.tci(), 0xa064);
+    assert_eq!(VlanTag::from_tci(0xb064), VlanTag { dei: true, ..tag });
+}
+
+#[test]
+fn parse_tagged_test() -> Result<()> {
+    let data = tagged(get_ident_req(), VlanTag::default());
+    let untagged = PacketIdentReq::try_from(get_ident_req().as_slice())?;
+    let packet = PacketIdentReq::try_from(data.as_slice())?;
+    assert_eq!(packet.vlan, Some(VlanTag::default()));
+    assert_eq!(packet.xid, untagged.xid);
+    assert_eq!(
+        packet.find::<NameOfStation>(),
+        untagged.find::<NameOfStation>()
+    );
+    assert_eq!(packet.to_vec(), data);
+    assert_eq!(packet.encoded_len(), data.len());
+
+    let data = tagged(get_ident_resp(), VlanTag::new(6, 12));
+    let packet = DcpPacket::parse_with(data.as_slice(), ParseOptions::strict())?.packet;
+    assert_eq!(packet.head().vlan, Some(VlanTag::new(6, 12)));
+    assert_eq!(packet.to_vec(), data);
+    let view = DcpFrameView::new(data.as_slice())?;
+    let untagged = get_ident_resp();
+    let untagged = DcpFrameView::new(untagged.as_slice())?;
+    assert!(view.blocks().eq(untagged.blocks()));
+    Ok(())
+}
+
+#[test]
+fn tagged_truncated_test() {
+    let data = tagged(get_ident_req(), VlanTag::default());
+    assert_eq!(
+        DcpHead::try_from(&data[..28]),
+        Err(DcpError::Truncated {
+            needed: 30,
+            got: 28
+        })
+    );
+}
+
+#[test]
+fn serialize_tagged_test() -> Result<()> {
+    let mut packet = PacketIdentReq::try_from(get_ident_req().as_slice())?;
+    packet.set_vlan(Some(VlanTag::default()));
+    assert_eq!(packet.to_vec(), tagged(get_ident_req(), VlanTag::default()));
+
+    // the response uses the tag of the request
+    let source = MacAddr::new(0x00, 0x1c, 0x06, 0x11, 0x42, 0x02);
+    let resp = PacketIdentResp::from_req(source, packet);
+    assert_eq!(resp.vlan, Some(VlanTag::default()));
+    Ok(())
+}
+
+#[test]
+fn min_frame_padding_test() -> Result<()> {
+    let data = get_set_resp();
+    let mut packet = PacketSetResp::try_from(data.as_slice())?;
+    packet.set_min_frame_padding(true);
+    let padded = packet.to_vec();
+    assert_eq!(padded.len(), 60);
+    assert_eq!(packet.encoded_len(), 60);
+    assert_eq!(&padded[..data.len()], data.as_slice());
+    assert!(padded[data.len()..].iter().all(|x| *x == 0));
+    let parsed = PacketSetResp::parse_with(padded.as_slice(), ParseOptions::strict())?;
+    assert_eq!(parsed.packet.blocks(), packet.blocks());
+    assert_eq!(parsed.packet, packet);
+
+    // the minimum does not count the tag
+    packet.set_vlan(Some(VlanTag::default()));
+    let padded = packet.to_vec();
+    assert_eq!(padded.len(), 64);
+    assert_eq!(&padded[..data.len() + 4], tagged(data, VlanTag::default()));
+
+    let mut req = PacketIdentReq::new(MacAddr::new(0x00, 0x1c, 0x06, 0x11, 0x42, 0x02));
+    req.set_vlan(Some(VlanTag::default()));
+    req.set_min_frame_padding(true);
+    assert_eq!(PacketIdentReq::try_from(req.to_vec().as_slice())?, req);
+
+    // frames above the minimum are not padded
+    let data = get_ident_resp();
+    let mut packet = PacketIdentResp::try_from(data.as_slice())?;
+    packet.set_min_frame_padding(true);
+    assert_eq!(packet.to_vec(), data);
+    Ok(())
+}